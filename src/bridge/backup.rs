@@ -0,0 +1,306 @@
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Kinds of resources that are imported, in the order in which they are created.
+///
+/// Resources can only reference resources of kinds that are created before them.
+pub(crate) const IMPORT_ORDER: [&str; 5] =
+    ["groups", "scenes", "schedules", "rules", "resourcelinks"];
+
+/// Identifiers of resources that were created by [`Bridge::import_resources`], mapped from their
+/// identifiers in the imported document.
+///
+/// [`Bridge::import_resources`]: crate::Bridge::import_resources
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ImportedIds {
+    /// Identifiers of created groups.
+    pub groups: HashMap<String, String>,
+    /// Identifiers of created scenes.
+    pub scenes: HashMap<String, String>,
+    /// Identifiers of created schedules.
+    pub schedules: HashMap<String, String>,
+    /// Identifiers of created rules.
+    pub rules: HashMap<String, String>,
+    /// Identifiers of created resourcelinks.
+    pub resourcelinks: HashMap<String, String>,
+}
+
+impl ImportedIds {
+    fn get(&self, kind: &str) -> Option<&HashMap<String, String>> {
+        match kind {
+            "groups" => Some(&self.groups),
+            "scenes" => Some(&self.scenes),
+            "schedules" => Some(&self.schedules),
+            "rules" => Some(&self.rules),
+            "resourcelinks" => Some(&self.resourcelinks),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn insert(&mut self, kind: &str, old_id: String, new_id: String) {
+        let ids = match kind {
+            "groups" => &mut self.groups,
+            "scenes" => &mut self.scenes,
+            "schedules" => &mut self.schedules,
+            "rules" => &mut self.rules,
+            "resourcelinks" => &mut self.resourcelinks,
+            _ => return,
+        };
+        ids.insert(old_id, new_id);
+    }
+
+    /// Returns the body of the request that recreates an exported resource.
+    ///
+    /// Attributes that are set by the bridge are removed and references to imported resources
+    /// are replaced by their new identifiers. Returns `None` if the resource can not be created.
+    pub(crate) fn creation_body(
+        &self,
+        kind: &str,
+        resource: &JsonValue,
+        username: &str,
+    ) -> Option<JsonValue> {
+        let resource = resource.as_object()?;
+        let body = match kind {
+            "groups" => {
+                let kind = resource.get("type").and_then(JsonValue::as_str);
+                if matches!(kind, Some("Luminaire" | "LightSource" | "Lightsource")) {
+                    return None;
+                }
+                copy(resource, &["name", "type", "class", "lights", "locations"])
+            }
+            "scenes" => {
+                let mut body = copy(
+                    resource,
+                    &[
+                        "name",
+                        "type",
+                        "recycle",
+                        "appdata",
+                        "picture",
+                        "lightstates",
+                    ],
+                );
+                match resource.get("group").and_then(JsonValue::as_str) {
+                    Some(group) => {
+                        let group = self.groups.get(group).map_or(group, String::as_str);
+                        body.insert("group".to_owned(), group.into());
+                    }
+                    None => {
+                        if let Some(v) = resource.get("lights") {
+                            body.insert("lights".to_owned(), v.clone());
+                        }
+                    }
+                }
+                body
+            }
+            "schedules" => {
+                let mut body = copy(
+                    resource,
+                    &[
+                        "name",
+                        "description",
+                        "command",
+                        "status",
+                        "autodelete",
+                        "recycle",
+                    ],
+                );
+                if let Some(v) = resource.get("localtime").or_else(|| resource.get("time")) {
+                    body.insert("localtime".to_owned(), v.clone());
+                }
+                if let Some(command) = body.get_mut("command") {
+                    self.rewrite_command(command, username);
+                }
+                body
+            }
+            "rules" => {
+                let mut body = copy(resource, &["name", "conditions", "actions", "recycle"]);
+                if let Some(v @ ("enabled" | "disabled")) =
+                    resource.get("status").and_then(JsonValue::as_str)
+                {
+                    body.insert("status".to_owned(), v.into());
+                }
+                for condition in array_mut(&mut body, "conditions") {
+                    self.rewrite_command(condition, username);
+                }
+                for action in array_mut(&mut body, "actions") {
+                    self.rewrite_command(action, username);
+                }
+                body
+            }
+            "resourcelinks" => {
+                let mut body = copy(
+                    resource,
+                    &["name", "description", "type", "classid", "links", "recycle"],
+                );
+                for link in array_mut(&mut body, "links") {
+                    if let Some(v) = link.as_str() {
+                        *link = self.rewrite_address(v, username).into();
+                    }
+                }
+                body
+            }
+            _ => return None,
+        };
+        Some(JsonValue::Object(body))
+    }
+
+    /// Rewrites the address and the body of a rule condition, rule action or schedule command.
+    fn rewrite_command(&self, command: &mut JsonValue, username: &str) {
+        if let Some(address) = command.get_mut("address") {
+            if let Some(v) = address.as_str() {
+                *address = self.rewrite_address(v, username).into();
+            }
+        }
+        if let Some(scene) = command.get_mut("body").and_then(|v| v.get_mut("scene")) {
+            if let Some(v) = scene.as_str().and_then(|v| self.scenes.get(v)) {
+                *scene = v.as_str().into();
+            }
+        }
+    }
+
+    /// Replaces the identifier of an imported resource and the username in an address like
+    /// `/api/<username>/groups/1/action`.
+    fn rewrite_address(&self, address: &str, username: &str) -> String {
+        let (prefix, path) = match address.strip_prefix("/api/") {
+            Some(v) => match v.find('/') {
+                Some(index) => (format!("/api/{}", username), &v[index..]),
+                None => return address.to_owned(),
+            },
+            None => (String::new(), address),
+        };
+        let mut segments: Vec<&str> = path.split('/').collect();
+        if let (Some(kind), Some(id)) = (segments.get(1), segments.get(2)) {
+            if let Some(new_id) = self.get(kind).and_then(|v| v.get(*id)) {
+                segments[2] = new_id;
+            }
+        }
+        format!("{}{}", prefix, segments.join("/"))
+    }
+}
+
+fn copy(resource: &Map<String, JsonValue>, keys: &[&str]) -> Map<String, JsonValue> {
+    keys.iter()
+        .filter_map(|&key| Some((key.to_owned(), resource.get(key)?.clone())))
+        .collect()
+}
+
+fn array_mut<'a>(
+    body: &'a mut Map<String, JsonValue>,
+    key: &str,
+) -> impl Iterator<Item = &'a mut JsonValue> {
+    body.get_mut(key)
+        .and_then(JsonValue::as_array_mut)
+        .into_iter()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn creation_body() {
+        let mut ids = ImportedIds::default();
+        ids.insert("groups", "1".into(), "5".into());
+        ids.insert("scenes", "abc".into(), "def".into());
+        ids.insert("schedules", "2".into(), "7".into());
+
+        let group = json!({"name": "a", "type": "Luminaire", "lights": ["1"]});
+        assert_eq!(ids.creation_body("groups", &group, "user"), None);
+        let group = json!({
+            "name": "a",
+            "type": "Room",
+            "class": "Office",
+            "lights": ["1"],
+            "state": {"any_on": true, "all_on": true}
+        });
+        assert_eq!(
+            ids.creation_body("groups", &group, "user"),
+            Some(json!({"name": "a", "type": "Room", "class": "Office", "lights": ["1"]}))
+        );
+
+        let scene = json!({
+            "name": "b",
+            "type": "GroupScene",
+            "group": "1",
+            "lights": ["1"],
+            "owner": "old",
+            "lightstates": {"1": {"on": true}}
+        });
+        assert_eq!(
+            ids.creation_body("scenes", &scene, "user"),
+            Some(json!({
+                "name": "b",
+                "type": "GroupScene",
+                "group": "5",
+                "lightstates": {"1": {"on": true}}
+            }))
+        );
+
+        let schedule = json!({
+            "name": "c",
+            "description": "",
+            "command": {
+                "address": "/api/old/groups/1/action",
+                "method": "PUT",
+                "body": {"scene": "abc"}
+            },
+            "time": "W127/T07:00:00",
+            "created": "2020-01-01T00:00:00",
+            "status": "enabled"
+        });
+        assert_eq!(
+            ids.creation_body("schedules", &schedule, "user"),
+            Some(json!({
+                "name": "c",
+                "description": "",
+                "command": {
+                    "address": "/api/user/groups/5/action",
+                    "method": "PUT",
+                    "body": {"scene": "def"}
+                },
+                "localtime": "W127/T07:00:00",
+                "status": "enabled"
+            }))
+        );
+
+        let rule = json!({
+            "name": "d",
+            "owner": "old",
+            "status": "resourcedeleted",
+            "conditions": [{"address": "/sensors/2/state/buttonevent", "operator": "dx"}],
+            "actions": [{"address": "/schedules/2", "method": "PUT", "body": {"status": "enabled"}}]
+        });
+        assert_eq!(
+            ids.creation_body("rules", &rule, "user"),
+            Some(json!({
+                "name": "d",
+                "conditions": [{"address": "/sensors/2/state/buttonevent", "operator": "dx"}],
+                "actions": [
+                    {"address": "/schedules/7", "method": "PUT", "body": {"status": "enabled"}}
+                ]
+            }))
+        );
+
+        let resourcelink = json!({
+            "name": "e",
+            "description": "",
+            "type": "Link",
+            "classid": 1,
+            "owner": "old",
+            "links": ["/scenes/abc", "/rules/3"]
+        });
+        assert_eq!(
+            ids.creation_body("resourcelinks", &resourcelink, "user"),
+            Some(json!({
+                "name": "e",
+                "description": "",
+                "type": "Link",
+                "classid": 1,
+                "links": ["/scenes/def", "/rules/3"]
+            }))
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::resource::{PublicConfig, RequestMethod};
+use crate::Result;
+use std::net::IpAddr;
+
+/// Returns the configuration of a bridge that can be accessed without a registered user.
+///
+/// This is useful to get information about a bridge before registering a user, e.g. the
+/// identifier, name or API version. The request is sent to port 80 without a proxy, use
+/// [`Bridge::get_public_config`] for bridges with another port, base path or proxy.
+///
+/// [`Bridge::get_public_config`]: crate::Bridge::get_public_config
+///
+/// # Examples
+///
+/// Print the identifier of a bridge:
+/// ```no_run
+/// use huelib::bridge;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// # fn main() -> Result<(), huelib::Error> {
+/// let bridge_ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+/// let config = bridge::get_public_config(bridge_ip)?;
+/// println!("Bridge `{}` has the identifier `{}`", config.name, config.bridge_id);
+/// # Ok(())
+/// # }
+/// ```
+pub fn get_public_config(ip_address: IpAddr) -> Result<PublicConfig> {
+    let url = format!("http://{}/api/config", ip_address);
+    let http_response = super::http_request(RequestMethod::Get, &url).call()?;
+    Ok(http_response.into_json()?)
+}
@@ -1,3 +1,4 @@
+use crate::resource::RequestMethod;
 use mime::Mime;
 use serde::{de::Error, Deserialize, Deserializer};
 use std::{net::IpAddr, str::FromStr};
@@ -31,7 +32,7 @@ impl Description {
     /// the descriptor file.
     pub fn get(ip_address: IpAddr) -> crate::Result<Self> {
         let url = format!("http://{}/description.xml", ip_address);
-        let http_response = ureq::get(&url).call()?;
+        let http_response = super::http_request(RequestMethod::Get, &url).call()?;
         Ok(serde_xml_rs::from_reader(http_response.into_reader())?)
     }
 }
@@ -1,11 +1,26 @@
-use crate::Result;
+use crate::{resource::RequestMethod, Result};
 use serde::Deserialize;
 use std::net::IpAddr;
 
-/// Discovers bridges in the local netowork using N-UPnP.
+/// A bridge that was discovered using N-UPnP.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct DiscoveredBridge {
+    /// Identifier of the bridge.
+    pub id: String,
+    /// IP address of the bridge in the local network.
+    #[serde(rename = "internalipaddress")]
+    pub ip_address: IpAddr,
+    /// Port of the bridge.
+    ///
+    /// Note that the port that is reported by the discovery service is usually the HTTPS port.
+    pub port: Option<u16>,
+}
+
+/// Discovers bridges in the local network using N-UPnP.
 ///
 /// This sends a HTTP GET request to [https://discovery.meethue.com], to get IP addresses of bridges
-/// that are in the local network.
+/// that are in the local network. See the [`discover_nupnp_bridges`] function if you also need the
+/// identifiers and ports of the bridges.
 ///
 /// [https://discovery.meethue.com]: https://discovery.meethue.com
 ///
@@ -31,16 +46,53 @@ use std::net::IpAddr;
 /// # }
 /// ```
 pub fn discover_nupnp() -> Result<Vec<IpAddr>> {
-    let http_response = ureq::get("https://discovery.meethue.com").call()?;
-    #[derive(Deserialize)]
-    struct BridgeJson {
-        #[serde(rename = "internalipaddress")]
-        ip_address: String,
-    }
-    let bridges: Vec<BridgeJson> = http_response.into_json()?;
-    let mut ip_addresses = Vec::<IpAddr>::new();
-    for b in bridges {
-        ip_addresses.push(b.ip_address.parse()?);
+    Ok(discover_nupnp_bridges()?
+        .into_iter()
+        .map(|v| v.ip_address)
+        .collect())
+}
+
+/// Discovers bridges in the local network using N-UPnP.
+///
+/// In comparison to [`discover_nupnp`], this function returns the identifiers and ports of the
+/// bridges in addition to the IP addresses.
+///
+/// # Examples
+///
+/// Print the identifier and IP address of all discovered bridges:
+/// ```no_run
+/// # fn main() -> Result<(), huelib::Error> {
+/// for bridge in huelib::bridge::discover_nupnp_bridges()? {
+///     println!("{}: {}", bridge.id, bridge.ip_address);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn discover_nupnp_bridges() -> Result<Vec<DiscoveredBridge>> {
+    let http_response =
+        super::http_request(RequestMethod::Get, "https://discovery.meethue.com").call()?;
+    Ok(http_response.into_json()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn deserialize_discovered_bridge() {
+        let json = json!({
+            "id": "001788fffe100491",
+            "internalipaddress": "192.168.2.23",
+            "port": 443
+        });
+        let value: DiscoveredBridge = serde_json::from_value(json).unwrap();
+        let expected = DiscoveredBridge {
+            id: "001788fffe100491".into(),
+            ip_address: IpAddr::V4(Ipv4Addr::new(192, 168, 2, 23)),
+            port: Some(443),
+        };
+        assert_eq!(value, expected);
     }
-    Ok(ip_addresses)
 }
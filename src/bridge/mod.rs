@@ -1,33 +1,334 @@
 use crate::resource::{self, Creator, Modifier, RequestMethod, Scanner};
-use crate::{response::Modified, Response, Result};
-use serde::de::DeserializeOwned;
+use crate::response::{Error as ResponseError, Modified};
+use crate::{Response, Result};
+use rate_limit::RateLimiter;
+use response_cache::ResponseCache;
+use seen_resources::SeenResources;
+use serde::de::{self, DeserializeOwned};
+use serde::Deserialize;
 use serde_json::Value as JsonValue;
-use std::{collections::HashMap, net::IpAddr};
+use std::cmp::Ordering;
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+use std::{collections::HashMap, fmt, marker::PhantomData, sync::Arc, thread};
+use warnings::WarningLog;
 
+mod backup;
+mod config;
 #[cfg(feature = "upnp-description")]
 mod description;
 mod discover;
+mod rate_limit;
+mod references;
 mod register;
+mod response_cache;
+mod seen_resources;
+mod session;
+mod shared;
+mod warnings;
 
+pub use backup::ImportedIds;
+pub use config::get_public_config;
 #[cfg(feature = "upnp-description")]
 pub use description::{
     description, Description, DescriptionDevice, DescriptionIcon, DescriptionSpecVersion,
 };
-pub use discover::discover_nupnp;
-pub use register::{register_user, register_user_with_clientkey};
+pub use discover::{discover_nupnp, discover_nupnp_bridges, DiscoveredBridge};
+pub use references::References;
+pub use register::{decode_clientkey, register_user, register_user_with_clientkey};
+pub use session::{CredentialStore, Credentials, Session};
+pub use warnings::Warning;
 
 type ResponsesModified = Vec<Response<Modified>>;
 
+/// Value of the `User-Agent` header that is sent with every request.
+const USER_AGENT: &str = concat!("huelib-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Interval in which a light is requested while waiting for it to reach a state.
+const CONFIRM_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Creates a HTTP request with the headers that are sent with every request.
+fn http_request(request_method: RequestMethod, url: &str) -> ureq::Request {
+    ureq::request(method_name(request_method), url).set("User-Agent", USER_AGENT)
+}
+
+/// Returns the name of a HTTP method.
+fn method_name(request_method: RequestMethod) -> &'static str {
+    match request_method {
+        RequestMethod::Put => "PUT",
+        RequestMethod::Post => "POST",
+        RequestMethod::Get => "GET",
+        RequestMethod::Delete => "DELETE",
+    }
+}
+
+/// Returns the URL of a bridge that the path of the Philips Hue API is appended to.
+fn base_url(ip_address: IpAddr, port: u16) -> String {
+    format!("http://{}", SocketAddr::new(ip_address, port))
+}
+
+/// Returns the URL to the Philips Hue API of a bridge.
+fn api_url(base_url: &str, username: &str) -> String {
+    format!("{}/api/{}", base_url, username)
+}
+
+/// Sends a HTTP request with a JSON body.
+fn send_json(request: ureq::Request, body: JsonValue) -> Result<ureq::Response> {
+    Ok(request
+        .set("Content-Type", "application/json")
+        .send_json(body)?)
+}
+
+/// Parses a JSON response body.
+fn parse_json<T>(text: &str) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    // Map the error like `ureq::Response::into_json` does, so that the error does not depend on
+    // how the body was read.
+    Ok(serde_json::from_str(text).map_err(std::io::Error::from)?)
+}
+
 fn parse_response<T>(response: JsonValue) -> crate::Result<T>
 where
     T: DeserializeOwned,
 {
-    if let Ok(mut v) = serde_json::from_value::<Vec<Response<JsonValue>>>(response.clone()) {
-        if let Some(v) = v.pop() {
-            v.into_result()?;
+    parse_response_collecting(response, None)
+}
+
+/// Parses a response like [`parse_response`] and adds attributes that are unknown to the type to
+/// `warnings`.
+fn parse_response_collecting<T>(
+    response: JsonValue,
+    warnings: Option<&mut Vec<Warning>>,
+) -> crate::Result<T>
+where
+    T: DeserializeOwned,
+{
+    if let JsonValue::Array(responses) = &response {
+        if let Some(error) = responses.last().and_then(|v| v.get("error")) {
+            return Err(ResponseError::deserialize(error)?.into());
         }
     }
-    Ok(serde_json::from_value(response)?)
+    match warnings {
+        Some(warnings) => Ok(serde_ignored::deserialize(response, |path| {
+            warnings.push(Warning::UnknownAttribute(path.to_string()))
+        })?),
+        None => Ok(serde_json::from_value(response)?),
+    }
+}
+
+/// Converts a map of resources into a vector that is sorted by their identifiers.
+///
+/// See [`compare_ids`] for how the identifiers are ordered.
+fn sorted_by_id<T, F>(map: HashMap<String, T>, with_id: F) -> Vec<T>
+where
+    F: Fn(T, String) -> T,
+{
+    let mut entries: Vec<(String, T)> = map.into_iter().collect();
+    entries.sort_by(|(a, _), (b, _)| compare_ids(a, b));
+    entries.into_iter().map(|(id, v)| with_id(v, id)).collect()
+}
+
+/// Compares identifiers of resources.
+///
+/// Numeric identifiers are compared as numbers, so `10` comes after `9`. Other identifiers, like
+/// the ones of scenes, are compared as strings and come after numeric identifiers.
+fn compare_ids(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+/// Records the identifier of the requested resource in the span of the current operation.
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+fn record_id(id: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("id", id);
+}
+
+/// Returns the first resource with the given name.
+///
+/// If no name matches exactly, the first name that matches case-insensitively is used.
+fn find_by_name<T, F>(resources: Vec<T>, name: &str, get_name: F) -> Option<T>
+where
+    F: Fn(&T) -> &str,
+{
+    match resources.iter().position(|v| get_name(v) == name) {
+        Some(index) => Some(resources.into_iter().nth(index)?),
+        None => resources
+            .into_iter()
+            .find(|v| get_name(v).to_lowercase() == name.to_lowercase()),
+    }
+}
+
+/// Returns the usernames of the users that have not been used for the given duration, except
+/// for the current user.
+fn unused_users(
+    config: &resource::Config,
+    older_than: chrono::Duration,
+    current_username: &str,
+) -> Vec<String> {
+    config
+        .whitelist
+        .iter()
+        .filter(|user| user.id != current_username)
+        .filter(|user| {
+            config
+                .current_time
+                .signed_duration_since(user.last_use_date)
+                > older_than
+        })
+        .map(|user| user.id.clone())
+        .collect()
+}
+
+/// Returns the addresses that reference a resource that does not exist.
+///
+/// `get_ids` returns the identifiers of all resources of a kind (e.g. `sensors`) and is called
+/// at most once per kind.
+fn unresolved_addresses<'a, I, F>(addresses: I, mut get_ids: F) -> Result<Vec<String>>
+where
+    I: IntoIterator<Item = &'a str>,
+    F: FnMut(&str) -> Result<Vec<String>>,
+{
+    const KINDS: &[&str] = &[
+        "lights",
+        "groups",
+        "scenes",
+        "schedules",
+        "sensors",
+        "rules",
+        "resourcelinks",
+    ];
+    let mut ids: HashMap<&str, Vec<String>> = HashMap::new();
+    let mut unresolved = Vec::new();
+    for address in addresses {
+        let mut segments = address.trim_start_matches('/').split('/');
+        let (kind, id) = match (segments.next(), segments.next()) {
+            (Some(kind), Some(id)) if KINDS.contains(&kind) => (kind, id),
+            _ => continue,
+        };
+        if kind == "groups" && id == "0" {
+            continue;
+        }
+        if !ids.contains_key(kind) {
+            ids.insert(kind, get_ids(kind)?);
+        }
+        let exists = ids[kind].iter().any(|v| v == id);
+        if !exists && !unresolved.iter().any(|v| v == address) {
+            unresolved.push(address.to_owned());
+        }
+    }
+    Ok(unresolved)
+}
+
+/// Part of the configuration of a bridge that contains the datastore version.
+#[derive(Deserialize)]
+struct DatastoreVersion {
+    #[serde(rename = "datastoreversion")]
+    datastore_version: String,
+}
+
+/// Identifier of a resource that was created with a raw request.
+#[derive(Deserialize)]
+struct CreatedId {
+    id: String,
+}
+
+/// Visitor that passes every entry of a map of resources to a function, without collecting them.
+struct ForEachVisitor<T, F> {
+    f: F,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T, F> de::Visitor<'de> for ForEachVisitor<T, F>
+where
+    T: de::Deserialize<'de>,
+    F: FnMut(String, T),
+{
+    type Value = std::result::Result<(), ResponseError>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map of resources or a list of responses")
+    }
+
+    fn visit_map<V: de::MapAccess<'de>>(
+        mut self,
+        mut map: V,
+    ) -> std::result::Result<Self::Value, V::Error> {
+        while let Some((id, resource)) = map.next_entry()? {
+            (self.f)(id, resource);
+        }
+        Ok(Ok(()))
+    }
+
+    fn visit_seq<V: de::SeqAccess<'de>>(
+        self,
+        mut seq: V,
+    ) -> std::result::Result<Self::Value, V::Error> {
+        let mut result = Ok(());
+        while let Some(response) = seq.next_element::<Response<JsonValue>>()? {
+            if let Err(e) = response.into_result() {
+                result = Err(e);
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// HTTP agent that is reused for all requests of a bridge.
+///
+/// The agent keeps connections alive, so consecutive requests do not have to connect again.
+/// Clones of the agent share the same connection pool.
+#[derive(Clone, Debug)]
+struct HttpAgent {
+    agent: ureq::Agent,
+    /// URL of the proxy that requests are sent through.
+    proxy_url: Option<String>,
+}
+
+impl HttpAgent {
+    fn new() -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().build(),
+            proxy_url: None,
+        }
+    }
+
+    fn with_proxy(proxy_url: String) -> Result<Self> {
+        let agent = ureq::AgentBuilder::new()
+            .proxy(ureq::Proxy::new(&proxy_url)?)
+            .build();
+        Ok(Self {
+            agent,
+            proxy_url: Some(proxy_url),
+        })
+    }
+
+    /// Creates a HTTP request with the headers that are sent with every request.
+    fn request(&self, request_method: RequestMethod, url: &str) -> ureq::Request {
+        self.agent
+            .request(method_name(request_method), url)
+            .set("User-Agent", USER_AGENT)
+    }
+}
+
+impl PartialEq for HttpAgent {
+    fn eq(&self, other: &Self) -> bool {
+        self.proxy_url == other.proxy_url
+    }
+}
+
+impl Eq for HttpAgent {}
+
+impl std::hash::Hash for HttpAgent {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.proxy_url.hash(state);
+    }
 }
 
 /// A bridge with IP address and username.
@@ -37,8 +338,22 @@ pub struct Bridge {
     username: String,
     /// IP address of the bridge.
     ip_address: IpAddr,
+    /// Port of the bridge.
+    port: u16,
+    /// URL that the path of the Philips Hue API is appended to, without a slash at the end.
+    base_url: String,
     /// Url to the Philips Hue API.
     api_url: String,
+    /// Rate limiter that is shared by all clones of the bridge.
+    rate_limiter: Option<Arc<RateLimiter>>,
+    /// Cache of parsed responses that is shared by all clones of the bridge.
+    response_cache: Option<Arc<ResponseCache>>,
+    /// New resources that were already returned, shared by all clones of the bridge.
+    seen_resources: Arc<SeenResources>,
+    /// Warnings of the last parsed response, shared by all clones of the bridge.
+    warnings: Option<Arc<WarningLog>>,
+    /// HTTP agent that is shared by all clones of the bridge.
+    agent: HttpAgent,
 }
 
 impl Bridge {
@@ -55,17 +370,239 @@ impl Bridge {
     /// let bridge = Bridge::new(ip, "username");
     /// ```
     pub fn new<S>(ip_address: IpAddr, username: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::new_with_port(ip_address, 80, username)
+    }
+
+    /// Creates a new bridge and checks that the username is valid.
+    ///
+    /// In comparison to [`new`], this returns [`Error::InvalidUsername`] if the username is empty
+    /// or contains characters that are not allowed in an URL path segment.
+    ///
+    /// [`new`]: Self::new
+    /// [`Error::InvalidUsername`]: crate::Error::InvalidUsername
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+    /// assert!(Bridge::try_new(ip, "username").is_ok());
+    /// assert!(Bridge::try_new(ip, "").is_err());
+    /// ```
+    pub fn try_new<S>(ip_address: IpAddr, username: S) -> Result<Self>
+    where
+        S: Into<String>,
+    {
+        let username = username.into();
+        let is_valid = !username.is_empty()
+            && username
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !is_valid {
+            return Err(crate::Error::InvalidUsername);
+        }
+        Ok(Self::new(ip_address, username))
+    }
+
+    /// Creates a new bridge that serves the API on the given port.
+    ///
+    /// This is useful for bridges behind a reverse proxy or emulated bridges.
+    ///
+    /// # Examples
+    ///
+    /// Create a bridge that listens on port 8080:
+    /// ```
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+    /// let bridge = Bridge::new_with_port(ip, 8080, "username");
+    /// ```
+    pub fn new_with_port<S>(ip_address: IpAddr, port: u16, username: S) -> Self
     where
         S: Into<String>,
     {
         let username = username.into();
+        let base_url = base_url(ip_address, port);
         Bridge {
-            api_url: format!("http://{}/api/{}", ip_address, username),
+            api_url: api_url(&base_url, &username),
+            base_url,
             username,
             ip_address,
+            port,
+            rate_limiter: None,
+            response_cache: None,
+            seen_resources: Arc::new(SeenResources::new()),
+            warnings: None,
+            agent: HttpAgent::new(),
+        }
+    }
+
+    /// Sets the URL that the path of the Philips Hue API is appended to.
+    ///
+    /// This is useful for bridges behind a reverse proxy, which can serve the API over HTTPS, on
+    /// another host or under a path prefix. The URL contains the scheme and host, a slash at the
+    /// end is ignored. The IP address and port of the bridge are not used for requests anymore.
+    ///
+    /// # Examples
+    ///
+    /// Send requests to `https://example.com/hue/api/username`:
+    /// ```
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+    /// let bridge = Bridge::new(ip, "username").with_base_url("https://example.com/hue/");
+    /// assert_eq!(bridge.base_url(), "https://example.com/hue");
+    /// ```
+    pub fn with_base_url<S>(self, base_url: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        let base_url = base_url.as_ref().trim_end_matches('/').to_owned();
+        Self {
+            api_url: api_url(&base_url, &self.username),
+            base_url,
+            ..self
         }
     }
 
+    /// Returns the URL that the path of the Philips Hue API is appended to.
+    ///
+    /// This is `http://<ip address>:<port>` unless another URL was set with [`with_base_url`].
+    ///
+    /// [`with_base_url`]: Self::with_base_url
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Limits the number of requests that are sent to the bridge per second.
+    ///
+    /// The bridge drops requests if too many are sent in a short time, Philips recommends to send
+    /// no more than 10 requests per second. If the limit is reached, requests are delayed until
+    /// they can be sent. Clones of the bridge share the same limit. A rate of 0 removes the
+    /// limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+    /// let bridge = Bridge::new(ip, "username").with_rate_limit(10);
+    /// ```
+    pub fn with_rate_limit(self, per_second: u32) -> Self {
+        Self {
+            rate_limiter: match per_second {
+                0 => None,
+                v => Some(Arc::new(RateLimiter::new(v))),
+            },
+            ..self
+        }
+    }
+
+    /// Returns the maximal number of requests per second, if the requests are limited.
+    pub fn rate_limit(&self) -> Option<u32> {
+        self.rate_limiter.as_ref().map(|v| v.per_second())
+    }
+
+    /// Caches the parsed response of [`get_all_lights`] and reuses it while the response is
+    /// unchanged.
+    ///
+    /// The bridge does not support conditional requests, so the response is still requested, but
+    /// parsing it is skipped if its body is identical to the previous one. This saves CPU time
+    /// when polling the lights frequently. The cache is cleared when a request that modifies the
+    /// bridge is sent. Clones of the bridge share the same cache.
+    ///
+    /// [`get_all_lights`]: Self::get_all_lights
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+    /// let bridge = Bridge::new(ip, "username").with_response_cache();
+    /// ```
+    pub fn with_response_cache(self) -> Self {
+        Self {
+            response_cache: Some(Arc::new(ResponseCache::new())),
+            ..self
+        }
+    }
+
+    /// Enables collecting warnings while parsing responses.
+    ///
+    /// Attributes of a response that are not known by this library are ignored while parsing. If
+    /// this is enabled, they are collected and can be inspected with [`last_warnings`], which
+    /// helps to notice changes in new firmwares of the bridge. Clones of the bridge share the
+    /// collected warnings.
+    ///
+    /// Attributes that are missing from a response are not reported. Many attributes are only
+    /// present for some devices or firmwares, so a missing attribute is parsed as `None` or its
+    /// default value, and a missing required attribute makes the request fail with
+    /// [`Error::ParseJson`].
+    ///
+    /// [`last_warnings`]: Self::last_warnings
+    /// [`Error::ParseJson`]: crate::Error::ParseJson
+    pub fn with_warnings(self) -> Self {
+        Self {
+            warnings: Some(Arc::new(WarningLog::new())),
+            ..self
+        }
+    }
+
+    /// Returns the warnings of the last parsed response.
+    ///
+    /// Always empty if warnings are not enabled with [`with_warnings`]. Only the responses of
+    /// requests for resources are checked, not the responses of modifications. Responses that are
+    /// taken from the response cache are not parsed again and have no warnings.
+    ///
+    /// [`with_warnings`]: Self::with_warnings
+    pub fn last_warnings(&self) -> Vec<Warning> {
+        self.warnings.as_ref().map(|v| v.get()).unwrap_or_default()
+    }
+
+    /// Sends all requests to the bridge through the given proxy.
+    ///
+    /// The URL has the format `http://<user>:<password>@<host>:<port>`, where everything except
+    /// the host is optional. Returns an error if the URL is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> huelib::Result<()> {
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+    /// let bridge = Bridge::new(ip, "username").with_proxy("http://localhost:8080")?;
+    /// assert_eq!(bridge.proxy(), Some("http://localhost:8080"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_proxy<S>(self, proxy_url: S) -> Result<Self>
+    where
+        S: Into<String>,
+    {
+        Ok(Self {
+            agent: HttpAgent::with_proxy(proxy_url.into())?,
+            ..self
+        })
+    }
+
+    /// Returns the URL of the proxy that requests are sent through, if a proxy is used.
+    pub fn proxy(&self) -> Option<&str> {
+        self.agent.proxy_url.as_deref()
+    }
+
     /// Returns the name of the user that is connected to the bridge.
     pub fn username(&self) -> &str {
         &self.username
@@ -76,6 +613,62 @@ impl Bridge {
         &self.ip_address
     }
 
+    /// Returns the port of the bridge.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Sends a HTTP request to the Philips Hue API and returns the HTTP response.
+    fn send_request(
+        &self,
+        url_suffix: &str,
+        request_method: RequestMethod,
+        body: Option<JsonValue>,
+    ) -> Result<ureq::Response> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire();
+        }
+        if request_method != RequestMethod::Get {
+            if let Some(cache) = &self.response_cache {
+                cache.clear();
+            }
+        }
+        let url = format!("{}/{}", self.api_url, url_suffix.trim_start_matches('/'));
+        let method = method_name(request_method);
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "http_request",
+            method,
+            path = url_suffix,
+            status = tracing::field::Empty,
+        )
+        .entered();
+        log::debug!("Sending {} request to {}", method, url_suffix);
+        if let Some(v) = &body {
+            log::trace!("Request body of {} {}: {}", method, url_suffix, v);
+        }
+        let request = self.agent.request(request_method, &url);
+        let result = match body {
+            Some(v) => send_json(request, v),
+            None => request.call().map_err(crate::Error::from),
+        };
+        match &result {
+            Ok(v) => log::debug!("{} {} returned status {}", method, url_suffix, v.status()),
+            Err(e) => log::debug!("{} {} failed: {}", method, url_suffix, e),
+        }
+        #[cfg(feature = "tracing")]
+        match &result {
+            Ok(v) => {
+                span.record("status", v.status());
+            }
+            Err(crate::Error::HttpStatus(code)) => {
+                span.record("status", code);
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
     /// Sends a HTTP request to the Philips Hue API and returns the response.
     pub(crate) fn api_request<S, T>(
         &self,
@@ -87,31 +680,287 @@ impl Bridge {
         S: AsRef<str>,
         T: DeserializeOwned,
     {
-        let url = format!("{}/{}", self.api_url, url_suffix.as_ref());
-        let request = match request_method {
-            RequestMethod::Put => ureq::put(&url),
-            RequestMethod::Post => ureq::post(&url),
-            RequestMethod::Get => ureq::get(&url),
-            RequestMethod::Delete => ureq::delete(&url),
-        };
-        let response = match body {
-            Some(v) => request.send_json(v)?,
-            None => request.call()?,
-        };
+        let url_suffix = url_suffix.as_ref();
+        let response = self.send_request(url_suffix, request_method, body)?;
+        if log::log_enabled!(log::Level::Trace) {
+            let text = response.into_string()?;
+            log::trace!("Response body of {}: {}", url_suffix, text);
+            return parse_json(&text);
+        }
         Ok(response.into_json()?)
     }
 
+    /// Parses a response and collects warnings if they are enabled.
+    fn parse_response<T>(&self, response: JsonValue) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        match &self.warnings {
+            Some(log) => {
+                let mut warnings = Vec::new();
+                let result = parse_response_collecting(response, Some(&mut warnings));
+                log.replace(warnings);
+                result
+            }
+            None => parse_response(response),
+        }
+    }
+
+    /// Sends a HTTP GET request for a map of resources and passes every resource with its
+    /// identifier to `f` while the response is read.
+    fn for_each_resource<T, F>(&self, url_suffix: &str, f: F) -> Result<()>
+    where
+        T: DeserializeOwned,
+        F: FnMut(String, T),
+    {
+        let response = self.send_request(url_suffix, RequestMethod::Get, None)?;
+        let mut deserializer = serde_json::Deserializer::from_reader(response.into_reader());
+        let visitor = ForEachVisitor {
+            f,
+            marker: PhantomData,
+        };
+        de::Deserializer::deserialize_any(&mut deserializer, visitor)??;
+        deserializer.end()?;
+        Ok(())
+    }
+
+    /// Returns whether the bridge responds to requests.
+    ///
+    /// This sends a lightweight request to the bridge and returns `false` if the bridge cannot be
+    /// connected to or the request timed out. Other errors are returned as they are.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn reachable(&self) -> Result<bool> {
+        match self.api_request::<_, JsonValue>("config", RequestMethod::Get, None) {
+            Ok(_) => Ok(true),
+            Err(crate::Error::Connection(_)) | Err(crate::Error::Timeout(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns whether the user of the bridge is authorized.
+    ///
+    /// The user is not authorized anymore if it was removed from the whitelist, for example
+    /// because the bridge was reset. See [`Error::is_unauthorized`] for checking errors that are
+    /// returned by other requests.
+    ///
+    /// [`Error::is_unauthorized`]: crate::Error::is_unauthorized
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn is_authorized(&self) -> Result<bool> {
+        match self.parse_response::<JsonValue>(self.api_request(
+            "capabilities",
+            RequestMethod::Get,
+            None,
+        )?) {
+            Ok(_) => Ok(true),
+            Err(e) if e.is_unauthorized() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Registers a new user if the user of the bridge is not authorized.
+    ///
+    /// Returns `None` if the user is authorized. Otherwise a new user is registered with the
+    /// given device type and its username is returned, which has to be persisted and used to
+    /// create a new [`Bridge`]. Registering a user requires the link button of the bridge to be
+    /// pressed, otherwise an error with [`ErrorKind::LinkButtonNotPressed`] is returned.
+    ///
+    /// [`ErrorKind::LinkButtonNotPressed`]: crate::response::ErrorKind::LinkButtonNotPressed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> huelib::Result<()> {
+    /// use huelib::Bridge;
+    /// use std::net::{IpAddr, Ipv4Addr};
+    ///
+    /// let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+    /// let mut bridge = Bridge::new(ip, "username");
+    /// if let Some(username) = bridge.ensure_authorized("example")? {
+    ///     println!("Registered new user `{}`", username);
+    ///     bridge = Bridge::new(ip, username);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn ensure_authorized<S>(&self, devicetype: S) -> Result<Option<String>>
+    where
+        S: AsRef<str>,
+    {
+        if self.is_authorized()? {
+            return Ok(None);
+        }
+        let url = format!("{}/api", self.base_url);
+        let request = self.agent.request(RequestMethod::Post, &url);
+        register::register_user_at(request, devicetype.as_ref()).map(Some)
+    }
+
+    /// Returns the unparsed response of a HTTP GET request.
+    ///
+    /// The URL suffix is appended to the API URL, e.g. `lights/1` for the light with the
+    /// identifier `1`. This can be used to inspect resources that cannot be deserialized into the
+    /// types of this crate. Errors returned from the bridge are part of the returned value.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn get_raw<S>(&self, url_suffix: S) -> Result<JsonValue>
+    where
+        S: AsRef<str>,
+    {
+        self.api_request(url_suffix, RequestMethod::Get, None)
+    }
+
+    /// Sends a HTTP PUT request with the given body and returns the unparsed response.
+    ///
+    /// This can be used for requests that are not supported by the types of this crate. Returns
+    /// [`Error::Response`] if the bridge responded with an error.
+    ///
+    /// [`Error::Response`]: crate::Error::Response
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn put_raw<S>(&self, url_suffix: S, body: &JsonValue) -> Result<JsonValue>
+    where
+        S: AsRef<str>,
+    {
+        self.parse_response(self.api_request(url_suffix, RequestMethod::Put, Some(body.clone()))?)
+    }
+
+    /// Sends a HTTP POST request with the given body and returns the unparsed response.
+    ///
+    /// This can be used for requests that are not supported by the types of this crate. Returns
+    /// [`Error::Response`] if the bridge responded with an error.
+    ///
+    /// [`Error::Response`]: crate::Error::Response
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn post_raw<S>(&self, url_suffix: S, body: &JsonValue) -> Result<JsonValue>
+    where
+        S: AsRef<str>,
+    {
+        self.parse_response(self.api_request(
+            url_suffix,
+            RequestMethod::Post,
+            Some(body.clone()),
+        )?)
+    }
+
     /// Modifies the configuration of the bridge.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn set_config(&self, modifier: &resource::config::Modifier) -> Result<ResponsesModified> {
         modifier.execute(self, ())
     }
 
+    /// Changes the Zigbee channel of the bridge.
+    ///
+    /// Returns [`Error::InvalidZigbeeChannel`] without sending a request if the channel is not
+    /// one of [`ZIGBEE_CHANNELS`]. The bridge needs some time to change the channel, during which
+    /// lights and sensors are not reachable. The current channel is available in
+    /// [`Config::zigbee_channel`].
+    ///
+    /// [`Error::InvalidZigbeeChannel`]: crate::Error::InvalidZigbeeChannel
+    /// [`ZIGBEE_CHANNELS`]: resource::config::ZIGBEE_CHANNELS
+    /// [`Config::zigbee_channel`]: resource::Config::zigbee_channel
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn set_zigbee_channel(&self, channel: u8) -> Result<ResponsesModified> {
+        resource::config::validate_zigbee_channel(channel)?;
+        self.set_config(&resource::config::Modifier::new().with_zigbee_channel(channel))
+    }
+
+    /// Installs the software updates that are ready to be installed.
+    ///
+    /// The bridge installs the updates of all devices (including lights) at once, updates cannot
+    /// be installed for single lights. The state of the update of a light is available in the
+    /// `software_update` field of [`Light`].
+    ///
+    /// [`Light`]: resource::Light
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn install_software_updates(&self) -> Result<ResponsesModified> {
+        self.api_request(
+            "config",
+            RequestMethod::Put,
+            Some(serde_json::json!({ "swupdate2": { "install": true } })),
+        )
+    }
+
     /// Returns the configuration of the bridge.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn get_config(&self) -> Result<resource::Config> {
-        parse_response(self.api_request("config", RequestMethod::Get, None)?)
+        self.parse_response(self.api_request("config", RequestMethod::Get, None)?)
+    }
+
+    /// Returns the configuration of the bridge that can be accessed without a registered user.
+    ///
+    /// In comparison to the [`get_public_config`] function, the request is sent to the port or
+    /// base URL of this bridge and through its proxy.
+    ///
+    /// [`get_public_config`]: get_public_config()
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn get_public_config(&self) -> Result<resource::PublicConfig> {
+        let url = format!("{}/api/config", self.base_url);
+        let response = self.agent.request(RequestMethod::Get, &url).call()?;
+        Ok(response.into_json()?)
+    }
+
+    /// Returns the version of the datastore of the bridge.
+    ///
+    /// This only parses the `datastoreversion` attribute of the configuration. Comparing the
+    /// version with a previously returned one is a cheap way to detect changes of the datastore
+    /// before requesting all resources.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn datastore_version(&self) -> Result<String> {
+        let config: DatastoreVersion =
+            self.parse_response(self.api_request("config", RequestMethod::Get, None)?)?;
+        Ok(config.datastore_version)
+    }
+
+    /// Deletes a user from the whitelist of the bridge.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn delete_user<S>(&self, username: S) -> Result<()>
+    where
+        S: Into<String>,
+    {
+        let response: Vec<Response<JsonValue>> = self.api_request(
+            format!("config/whitelist/{}", username.into()),
+            RequestMethod::Delete,
+            None,
+        )?;
+        for i in response {
+            i.into_result()?;
+        }
+        Ok(())
+    }
+
+    /// Deletes all users that have not been used for the given duration.
+    ///
+    /// The last use of a user is compared to the current time of the bridge. The user of this
+    /// bridge is never deleted. The users are deleted one after another, even if deleting one of
+    /// them fails. Returns the username of every user that was selected for deletion, paired with
+    /// the result of deleting it.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn prune_unused_users(
+        &self,
+        older_than: std::time::Duration,
+    ) -> Result<Vec<(String, Result<()>)>> {
+        let older_than = match chrono::Duration::from_std(older_than) {
+            Ok(v) => v,
+            Err(_) => return Ok(Vec::new()),
+        };
+        let config = self.get_config()?;
+        Ok(self.delete_users(unused_users(&config, older_than, &self.username)))
+    }
+
+    /// Deletes the given users, pairing each username with the result of deleting it.
+    fn delete_users(&self, usernames: Vec<String>) -> Vec<(String, Result<()>)> {
+        usernames
+            .into_iter()
+            .map(|username| {
+                let result = self.delete_user(username.as_str());
+                (username, result)
+            })
+            .collect()
     }
 
     /// Modifies attributes of a light.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn set_light_attribute<S>(
         &self,
         id: S,
@@ -120,10 +969,16 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
     }
 
     /// Modifies the state of a light.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn set_light_state<S>(
         &self,
         id: S,
@@ -132,16 +987,166 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
+    }
+
+    /// Modifies the state of a light and waits until the light reports the new state.
+    ///
+    /// After the modifier is sent, the light is requested repeatedly until its state matches the
+    /// modifier (see [`StateModifier::matches`]) and the light is returned. Because the state is
+    /// requested again while a transition is in progress, the timeout should be longer than the
+    /// transition time. Returns [`Error::StateNotReached`] if the state does not match before the
+    /// timeout.
+    ///
+    /// [`StateModifier::matches`]: resource::light::StateModifier::matches
+    /// [`Error::StateNotReached`]: crate::Error::StateNotReached
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn set_light_state_and_confirm<S>(
+        &self,
+        id: S,
+        modifier: &resource::light::StateModifier,
+        timeout: Duration,
+    ) -> Result<resource::Light>
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        record_id(&id);
+        for i in self.set_light_state(id.as_str(), modifier)? {
+            i.into_result()?;
+        }
+        let deadline = Instant::now() + timeout;
+        loop {
+            let light = self.get_light(id.as_str())?;
+            if modifier.matches(&light.state) {
+                return Ok(light);
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining == Duration::ZERO {
+                return Err(crate::Error::StateNotReached(id));
+            }
+            thread::sleep(remaining.min(CONFIRM_POLL_INTERVAL));
+        }
+    }
+
+    /// Modifies the configuration of a light.
+    ///
+    /// If the light does not support a modified attribute, for example the startup behavior, the
+    /// bridge returns an error response for the attribute.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn set_light_config<S>(
+        &self,
+        id: S,
+        modifier: &resource::light::ConfigModifier,
+    ) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
+    }
+
+    /// Sets the color temperature of a light, clamped into the range that is supported by the
+    /// light.
+    ///
+    /// The light is requested first to get its supported range (see
+    /// [`Light::clamp_color_temperature`]). Returns the color temperature that was set.
+    ///
+    /// [`Light::clamp_color_temperature`]: resource::Light::clamp_color_temperature
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn set_light_color_temperature<S>(&self, id: S, value: u16) -> Result<u16>
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        record_id(&id);
+        let value = self.get_light(id.as_str())?.clamp_color_temperature(value);
+        let modifier = resource::light::StateModifier::new()
+            .with_color_temperature(resource::Adjust::Override(value));
+        for i in self.set_light_state(id, &modifier)? {
+            i.into_result()?;
+        }
+        Ok(value)
+    }
+
+    /// Sets the brightness of a light in percent.
+    ///
+    /// 1% is the lowest brightness that the light can display and 100% the highest (see
+    /// [`Light::brightness_from_percent`]), 0% turns the light off. Otherwise the light is
+    /// requested first to get its minimal dim level and turned on. Returns the brightness that was
+    /// set, or 0 if the light was turned off.
+    ///
+    /// [`Light::brightness_from_percent`]: resource::Light::brightness_from_percent
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn set_light_brightness<S>(&self, id: S, percent: u8) -> Result<u8>
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        record_id(&id);
+        let (modifier, value) = match percent {
+            0 => (resource::light::StateModifier::new().with_on(false), 0),
+            v => {
+                let value = self.get_light(id.as_str())?.brightness_from_percent(v);
+                let modifier = resource::light::StateModifier::new()
+                    .with_on(true)
+                    .with_brightness(resource::Adjust::Override(value));
+                (modifier, value)
+            }
+        };
+        for i in self.set_light_state(id, &modifier)? {
+            i.into_result()?;
+        }
+        Ok(value)
+    }
+
+    /// Makes a light perform one breathe cycle, so it can be found physically.
+    ///
+    /// This sets the alert effect of the light to [`Alert::Select`]. Newer bridges also provide
+    /// a dedicated identify command in the Hue API v2, which is not supported by this library.
+    ///
+    /// [`Alert::Select`]: resource::Alert::Select
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn identify_light<S>(&self, id: S) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        record_id(&id);
+        let modifier = resource::light::StateModifier::new().with_alert(resource::Alert::Select);
+        self.set_light_state(id, &modifier)
     }
 
     /// Returns a light.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn get_light<S>(&self, id: S) -> Result<resource::Light>
     where
         S: Into<String>,
     {
         let id = id.into();
-        let light: resource::Light = parse_response(self.api_request(
+        record_id(&id);
+        let light: resource::Light = self.parse_response(self.api_request(
             format!("lights/{}", id),
             RequestMethod::Get,
             None,
@@ -149,13 +1154,81 @@ impl Bridge {
         Ok(light.with_id(id))
     }
 
-    /// Returns all lights that are connected to the bridge.
+    /// Returns the capabilities of a light.
+    ///
+    /// Returns `None` if the light does not report its capabilities.
+    #[cfg(not(feature = "old-api"))]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn get_light_capabilities<S>(&self, id: S) -> Result<Option<resource::light::Capabilities>>
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        record_id(&id);
+        Ok(self.get_light(id)?.capabilities)
+    }
+
+    /// Returns all lights that are connected to the bridge, sorted by their identifiers.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn get_all_lights(&self) -> Result<Vec<resource::Light>> {
-        let map: HashMap<String, resource::Light> =
-            parse_response(self.api_request("lights", RequestMethod::Get, None)?)?;
-        Ok(map
+        let parse = |response: JsonValue| -> Result<Vec<resource::Light>> {
+            let map: HashMap<String, resource::Light> = self.parse_response(response)?;
+            Ok(sorted_by_id(map, resource::Light::with_id))
+        };
+        match &self.response_cache {
+            Some(cache) => {
+                let text = self
+                    .send_request("lights", RequestMethod::Get, None)?
+                    .into_string()?;
+                log::trace!("Response body of lights: {}", text);
+                // The warnings are replaced if the response is parsed, a cached response has none.
+                if let Some(log) = &self.warnings {
+                    log.replace(Vec::new());
+                }
+                cache.lights(&text, |v| parse(parse_json(v)?))
+            }
+            None => parse(self.api_request("lights", RequestMethod::Get, None)?),
+        }
+    }
+
+    /// Returns the light with the given name.
+    ///
+    /// If no light has exactly the given name, the name is compared case-insensitively. If
+    /// multiple lights have the same name, the first one that is returned by the bridge is used.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn get_light_by_name<S>(&self, name: S) -> Result<Option<resource::Light>>
+    where
+        S: AsRef<str>,
+    {
+        Ok(find_by_name(self.get_all_lights()?, name.as_ref(), |v| {
+            &v.name
+        }))
+    }
+
+    /// Calls `f` for every light that is connected to the bridge.
+    ///
+    /// In comparison to [`get_all_lights`], the lights are deserialized one at a time while the
+    /// response is read, so they never have to be held in memory all at once.
+    ///
+    /// [`get_all_lights`]: Self::get_all_lights
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn for_each_light<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(resource::Light),
+    {
+        self.for_each_resource("lights", |id, light: resource::Light| f(light.with_id(id)))
+    }
+
+    /// Returns all lights that can be reached by the bridge.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn get_reachable_lights(&self) -> Result<Vec<resource::Light>> {
+        Ok(self
+            .get_all_lights()?
             .into_iter()
-            .map(|(id, light)| light.with_id(id))
+            .filter(resource::Light::is_reachable)
             .collect())
     }
 
@@ -171,25 +1244,60 @@ impl Bridge {
     /// function.
     ///
     /// [`get_new_lights`]: #method.get_new_lights
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn search_new_lights(&self, scanner: &resource::light::Scanner) -> Result<()> {
-        scanner.execute(self)
+        scanner.execute(self)?;
+        self.seen_resources.reset_lights();
+        Ok(())
     }
 
     /// Returns discovered lights.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn get_new_lights(&self) -> Result<resource::Scan> {
-        parse_response(self.api_request("lights/new", RequestMethod::Get, None)?)
+        self.parse_response(self.api_request("lights/new", RequestMethod::Get, None)?)
+    }
+
+    /// Returns discovered lights that were not returned by a previous call of this method.
+    ///
+    /// The returned lights are remembered by the bridge and all of its clones, so lights that are
+    /// discovered while a scan is active are only returned once. In comparison to
+    /// [`get_new_lights`], this can be polled to show newly paired lights.
+    ///
+    /// The remembered lights are forgotten when a new scan is started with
+    /// [`search_new_lights`], when the bridge reports that another scan was started, or when
+    /// [`reset_new_lights_since_last`] is called.
+    ///
+    /// [`get_new_lights`]: Self::get_new_lights
+    /// [`search_new_lights`]: Self::search_new_lights
+    /// [`reset_new_lights_since_last`]: Self::reset_new_lights_since_last
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn get_new_lights_since_last(&self) -> Result<resource::Scan> {
+        let mut scan = self.get_new_lights()?;
+        self.seen_resources.retain_unseen_lights(&mut scan);
+        Ok(scan)
+    }
+
+    /// Forgets the lights that were returned by [`get_new_lights_since_last`], so they are
+    /// returned again.
+    ///
+    /// [`get_new_lights_since_last`]: Self::get_new_lights_since_last
+    pub fn reset_new_lights_since_last(&self) {
+        self.seen_resources.reset_lights();
     }
 
     /// Deletes a light from the bridge.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn delete_light<S>(&self, id: S) -> Result<()>
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("lights/{}", id.into()),
-            RequestMethod::Delete,
-            None,
-        )?;
+        let id = id.into();
+        record_id(&id);
+        let response: Vec<Response<JsonValue>> =
+            self.api_request(format!("lights/{}", id), RequestMethod::Delete, None)?;
         for i in response {
             i.into_result()?;
         }
@@ -197,11 +1305,47 @@ impl Bridge {
     }
 
     /// Creates a new group.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn create_group(&self, creator: &resource::group::Creator) -> Result<String> {
         creator.execute(self)
     }
 
+    /// Creates a new group and returns it.
+    ///
+    /// This is the same as calling [`create_group`] followed by [`get_group`] with the identifier
+    /// of the new group.
+    ///
+    /// [`create_group`]: Self::create_group
+    /// [`get_group`]: Self::get_group
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn create_group_full(&self, creator: &resource::group::Creator) -> Result<resource::Group> {
+        let id = self.create_group(creator)?;
+        self.get_group(id)
+    }
+
+    /// Creates a new group if no group with the same name exists.
+    ///
+    /// Returns the identifier of the existing group or of the newly created group. This makes it
+    /// safe to retry the request if the response got lost after the bridge created the group. The
+    /// groups are only matched by their name, so this is a best-effort check and does not work if
+    /// multiple groups are expected to have the same name.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn create_group_idempotent(&self, creator: &resource::group::Creator) -> Result<String> {
+        match self
+            .get_all_groups()?
+            .into_iter()
+            .find(|group| group.name == creator.name)
+        {
+            Some(group) => Ok(group.id),
+            None => creator.execute(self),
+        }
+    }
+
     /// Modifies attributes of a group.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn set_group_attribute<S>(
         &self,
         id: S,
@@ -210,10 +1354,74 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
+    }
+
+    /// Adds a light to a group.
+    ///
+    /// The current lights of the group are requested and the light is appended to them. Nothing is
+    /// sent to the bridge if the light is already in the group. A light can only be in one room,
+    /// so the bridge responds with an error when adding a light to a room while it is in another
+    /// room.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn add_light_to_group<S, T>(&self, group_id: S, light_id: T) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        let group_id = group_id.into();
+        record_id(&group_id);
+        let light_id = light_id.into();
+        let mut lights = self.get_group(group_id.clone())?.lights;
+        if lights.contains(&light_id) {
+            return Ok(Vec::new());
+        }
+        lights.push(light_id);
+        let modifier = resource::group::AttributeModifier::new().with_lights(lights);
+        self.set_group_attribute(group_id, &modifier)
+    }
+
+    /// Removes a light from a group.
+    ///
+    /// The current lights of the group are requested and the light is removed from them. Nothing
+    /// is sent to the bridge if the light is not in the group. Only rooms can be empty, so the
+    /// bridge responds with an error when removing the last light of any other group.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn remove_light_from_group<S, T>(
+        &self,
+        group_id: S,
+        light_id: T,
+    ) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        let group_id = group_id.into();
+        record_id(&group_id);
+        let light_id = light_id.into();
+        let mut lights = self.get_group(group_id.clone())?.lights;
+        let len = lights.len();
+        lights.retain(|v| v != &light_id);
+        if lights.len() == len {
+            return Ok(Vec::new());
+        }
+        let modifier = resource::group::AttributeModifier::new().with_lights(lights);
+        self.set_group_attribute(group_id, &modifier)
     }
 
     /// Modifies the state of a group.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn set_group_state<S>(
         &self,
         id: S,
@@ -222,16 +1430,58 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
+    }
+
+    /// Modifies the state of all lights.
+    ///
+    /// This modifies the state of the special group `0`, which contains all lights of the bridge.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn set_all_lights_state(
+        &self,
+        modifier: &resource::group::StateModifier,
+    ) -> Result<ResponsesModified> {
+        self.set_group_state("0", modifier)
+    }
+
+    /// Activates or deactivates streaming for an entertainment group.
+    ///
+    /// Activating the stream claims the entertainment group for this user. If another user is
+    /// already streaming to the group, the response contains an error with the kind
+    /// [`CannotClaimStreamOwnership`].
+    ///
+    /// [`CannotClaimStreamOwnership`]: crate::response::ErrorKind::CannotClaimStreamOwnership
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn set_group_stream_active<S>(&self, id: S, active: bool) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        record_id(&id);
+        self.api_request(
+            format!("groups/{}", id),
+            RequestMethod::Put,
+            Some(serde_json::json!({ "stream": { "active": active } })),
+        )
     }
 
     /// Returns a group.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn get_group<S>(&self, id: S) -> Result<resource::Group>
     where
         S: Into<String>,
     {
         let id = id.into();
-        let group: resource::Group = parse_response(self.api_request(
+        record_id(&id);
+        let group: resource::Group = self.parse_response(self.api_request(
             format!("groups/{}", id),
             RequestMethod::Get,
             None,
@@ -239,26 +1489,94 @@ impl Bridge {
         Ok(group.with_id(id))
     }
 
-    /// Returns all groups.
+    /// Returns all groups, sorted by their identifiers.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn get_all_groups(&self) -> Result<Vec<resource::Group>> {
         let map: HashMap<String, resource::Group> =
-            parse_response(self.api_request("groups", RequestMethod::Get, None)?)?;
-        Ok(map
+            self.parse_response(self.api_request("groups", RequestMethod::Get, None)?)?;
+        Ok(sorted_by_id(map, resource::Group::with_id))
+    }
+
+    /// Returns all groups of the kind `Room`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn get_rooms(&self) -> Result<Vec<resource::Group>> {
+        self.get_groups_of_kind(resource::group::CreatableKind::Room)
+    }
+
+    /// Returns all groups of the kind `Zone`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn get_zones(&self) -> Result<Vec<resource::Group>> {
+        self.get_groups_of_kind(resource::group::CreatableKind::Zone)
+    }
+
+    /// Returns all groups of the given kind.
+    fn get_groups_of_kind(
+        &self,
+        kind: resource::group::CreatableKind,
+    ) -> Result<Vec<resource::Group>> {
+        let kind = resource::group::Kind::Creatable(kind);
+        Ok(self
+            .get_all_groups()?
             .into_iter()
-            .map(|(id, group)| group.with_id(id))
+            .filter(|group| group.kind == kind)
             .collect())
     }
 
+    /// Returns the group with the given name.
+    ///
+    /// If no group has exactly the given name, the name is compared case-insensitively. If
+    /// multiple groups have the same name, the first one that is returned by the bridge is used.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn get_group_by_name<S>(&self, name: S) -> Result<Option<resource::Group>>
+    where
+        S: AsRef<str>,
+    {
+        Ok(find_by_name(self.get_all_groups()?, name.as_ref(), |v| {
+            &v.name
+        }))
+    }
+
+    /// Calls `f` for every group.
+    ///
+    /// In comparison to [`get_all_groups`], the groups are deserialized one at a time while the
+    /// response is read, so they never have to be held in memory all at once.
+    ///
+    /// [`get_all_groups`]: Self::get_all_groups
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn for_each_group<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(resource::Group),
+    {
+        self.for_each_resource("groups", |id, group: resource::Group| f(group.with_id(id)))
+    }
+
+    /// Returns a group together with the scenes that are linked to it.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn get_room<S>(&self, id: S) -> Result<crate::Room<'_>>
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        record_id(&id);
+        crate::Room::get(self, id)
+    }
+
     /// Deletes a group from the bridge.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn delete_group<S>(&self, id: S) -> Result<()>
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("groups/{}", id.into()),
-            RequestMethod::Delete,
-            None,
-        )?;
+        let id = id.into();
+        record_id(&id);
+        let response: Vec<Response<JsonValue>> =
+            self.api_request(format!("groups/{}", id), RequestMethod::Delete, None)?;
         for i in response {
             i.into_result()?;
         }
@@ -266,11 +1584,81 @@ impl Bridge {
     }
 
     /// Creates a new scene.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn create_scene(&self, creator: &resource::scene::Creator) -> Result<String> {
         creator.execute(self)
     }
 
+    /// Creates a new scene and returns it.
+    ///
+    /// This is the same as calling [`create_scene`] followed by [`get_scene`] with the identifier
+    /// of the new scene.
+    ///
+    /// [`create_scene`]: Self::create_scene
+    /// [`get_scene`]: Self::get_scene
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn create_scene_full(&self, creator: &resource::scene::Creator) -> Result<resource::Scene> {
+        let id = self.create_scene(creator)?;
+        self.get_scene(id)
+    }
+
+    /// Creates a new scene if no scene with the same name exists.
+    ///
+    /// Returns the identifier of the existing scene or of the newly created scene. This makes it
+    /// safe to retry the request if the response got lost after the bridge created the scene. The
+    /// scenes are only matched by their name, so this is a best-effort check and does not work if
+    /// multiple scenes are expected to have the same name.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn create_scene_idempotent(&self, creator: &resource::scene::Creator) -> Result<String> {
+        match self
+            .get_all_scenes()?
+            .into_iter()
+            .find(|scene| scene.name == creator.name)
+        {
+            Some(scene) => Ok(scene.id),
+            None => creator.execute(self),
+        }
+    }
+
+    /// Creates a new scene that stores the current state of the given lights.
+    ///
+    /// The scene is created without light states, so the bridge stores the current states of the
+    /// lights. Returns [`Error::UnresolvedAddresses`] with the addresses of lights that do not
+    /// exist, or [`Error::MissingAttribute`] if no light is given.
+    ///
+    /// [`Error::UnresolvedAddresses`]: crate::Error::UnresolvedAddresses
+    /// [`Error::MissingAttribute`]: crate::Error::MissingAttribute
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn save_current_as_scene<S, T>(&self, name: S, light_ids: &[T]) -> Result<String>
+    where
+        S: Into<String>,
+        T: AsRef<str>,
+    {
+        if light_ids.is_empty() {
+            return Err(crate::Error::MissingAttribute("lights"));
+        }
+        let lights: HashMap<String, JsonValue> =
+            self.parse_response(self.api_request("lights", RequestMethod::Get, None)?)?;
+        let unresolved: Vec<String> = light_ids
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|id| !lights.contains_key(*id))
+            .map(|id| format!("/lights/{}", id))
+            .collect();
+        if !unresolved.is_empty() {
+            return Err(crate::Error::UnresolvedAddresses(unresolved));
+        }
+        let light_ids = light_ids.iter().map(|v| v.as_ref().to_owned()).collect();
+        let creator = resource::scene::Creator::new(name.into(), light_ids)
+            .with_kind(resource::scene::Kind::LightScene);
+        creator.execute(self)
+    }
+
     /// Modifies the state and attributes of a scene.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn set_scene<S>(
         &self,
         id: S,
@@ -279,16 +1667,105 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
+    }
+
+    /// Modifies the stored state of a single light in a scene.
+    ///
+    /// In comparison to [`set_scene`], the states of the other lights in the scene are not sent
+    /// and stay unchanged. The transition time of the modifier is used when the scene is recalled.
+    ///
+    /// [`set_scene`]: Self::set_scene
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn set_scene_light_state<S, T>(
+        &self,
+        scene_id: S,
+        light_id: T,
+        modifier: &resource::light::StaticStateModifier,
+    ) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        let scene_id = scene_id.into();
+        record_id(&scene_id);
+        self.api_request(
+            format!("scenes/{}/lightstates/{}", scene_id, light_id.into()),
+            RequestMethod::Put,
+            Some(serde_json::to_value(modifier)?),
+        )
+    }
+
+    /// Removes a light from a scene.
+    ///
+    /// The current lights of the scene are requested and the light is removed from them. The
+    /// bridge also removes the stored state of the light. Nothing is sent to the bridge if the
+    /// light is not in the scene. A scene must contain at least one light, so the bridge responds
+    /// with an error when removing the last light; delete the scene instead.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn remove_light_from_scene<S, T>(
+        &self,
+        scene_id: S,
+        light_id: T,
+    ) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        let scene_id = scene_id.into();
+        record_id(&scene_id);
+        let light_id = light_id.into();
+        let mut lights = self.get_scene(scene_id.clone())?.lights.unwrap_or_default();
+        let len = lights.len();
+        lights.retain(|v| v != &light_id);
+        if lights.len() == len {
+            return Ok(Vec::new());
+        }
+        let modifier = resource::scene::Modifier::new().with_lights(lights);
+        self.set_scene(scene_id, &modifier)
+    }
+
+    /// Creates a copy of a scene with another name and returns the identifier of the new scene.
+    ///
+    /// The type of the scene, the group of a group scene and the states of the lights are copied
+    /// (see [`scene::Creator::from_scene`]).
+    ///
+    /// [`scene::Creator::from_scene`]: resource::scene::Creator::from_scene
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn duplicate_scene<S, N>(&self, id: S, name: N) -> Result<String>
+    where
+        S: Into<String>,
+        N: Into<String>,
+    {
+        let id = id.into();
+        record_id(&id);
+        let scene = self.get_scene(id)?;
+        self.create_scene(&resource::scene::Creator::from_scene(&scene, name.into()))
     }
 
     /// Returns a scene.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn get_scene<S>(&self, id: S) -> Result<resource::Scene>
     where
         S: Into<String>,
     {
         let id = id.into();
-        let scene: resource::Scene = parse_response(self.api_request(
+        record_id(&id);
+        let scene: resource::Scene = self.parse_response(self.api_request(
             format!("scenes/{}", id),
             RequestMethod::Get,
             None,
@@ -296,43 +1773,171 @@ impl Bridge {
         Ok(scene.with_id(id))
     }
 
-    /// Returns all scenes.
+    /// Returns all scenes, sorted by their identifiers.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn get_all_scenes(&self) -> Result<Vec<resource::Scene>> {
         let map: HashMap<String, resource::Scene> =
-            parse_response(self.api_request("scenes", RequestMethod::Get, None)?)?;
-        Ok(map
+            self.parse_response(self.api_request("scenes", RequestMethod::Get, None)?)?;
+        Ok(sorted_by_id(map, resource::Scene::with_id))
+    }
+
+    /// Returns all scenes that are linked to the given group.
+    ///
+    /// Scenes of the kind `LightScene` are not linked to a group and are never returned.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn get_scenes_for_group<S>(&self, group_id: S) -> Result<Vec<resource::Scene>>
+    where
+        S: Into<String>,
+    {
+        let group_id = group_id.into();
+        record_id(&group_id);
+        Ok(self
+            .get_all_scenes()?
             .into_iter()
-            .map(|(id, scene)| scene.with_id(id))
+            .filter(|scene| {
+                scene.kind == resource::scene::Kind::GroupScene
+                    && scene.group.as_ref() == Some(&group_id)
+            })
             .collect())
     }
 
+    /// Returns the scene with the given name.
+    ///
+    /// If no scene has exactly the given name, the name is compared case-insensitively. If
+    /// multiple scenes have the same name, the first one that is returned by the bridge is used.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn get_scene_by_name<S>(&self, name: S) -> Result<Option<resource::Scene>>
+    where
+        S: AsRef<str>,
+    {
+        Ok(find_by_name(self.get_all_scenes()?, name.as_ref(), |v| {
+            &v.name
+        }))
+    }
+
+    /// Calls `f` for every scene.
+    ///
+    /// In comparison to [`get_all_scenes`], the scenes are deserialized one at a time while the
+    /// response is read, so they never have to be held in memory all at once.
+    ///
+    /// [`get_all_scenes`]: Self::get_all_scenes
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn for_each_scene<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(resource::Scene),
+    {
+        self.for_each_resource("scenes", |id, scene: resource::Scene| f(scene.with_id(id)))
+    }
+
+    /// Recalls a scene.
+    ///
+    /// Scenes of kind [`GroupScene`] are recalled on the group they are linked to. Scenes of kind
+    /// [`LightScene`] are recalled on the group containing all lights, which only changes the
+    /// lights that are part of the scene.
+    ///
+    /// [`GroupScene`]: resource::scene::Kind::GroupScene
+    /// [`LightScene`]: resource::scene::Kind::LightScene
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
+    pub fn recall_scene<S>(&self, id: S) -> Result<ResponsesModified>
+    where
+        S: Into<String>,
+    {
+        let id = id.into();
+        record_id(&id);
+        let scene = self.get_scene(id)?;
+        let group_id = match (scene.kind, scene.group) {
+            (resource::scene::Kind::GroupScene, Some(group_id)) => group_id,
+            _ => "0".to_owned(),
+        };
+        let modifier = resource::group::StateModifier::new().with_scene(scene.id);
+        self.set_group_state(group_id, &modifier)
+    }
+
     /// Deletes a scene.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn delete_scene<S>(&self, id: S) -> Result<()>
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("scenes/{}", id.into()),
-            RequestMethod::Delete,
-            None,
-        )?;
+        let id = id.into();
+        record_id(&id);
+        let response: Vec<Response<JsonValue>> =
+            self.api_request(format!("scenes/{}", id), RequestMethod::Delete, None)?;
         for i in response {
             i.into_result()?;
         }
         Ok(())
     }
 
+    /// Deletes multiple scenes.
+    ///
+    /// The scenes are deleted one after another, even if deleting one of them fails. The results
+    /// are returned in the order of the identifiers.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn delete_scenes<S>(&self, ids: &[S]) -> Vec<Result<()>>
+    where
+        S: AsRef<str>,
+    {
+        ids.iter()
+            .map(|id| self.delete_scene(id.as_ref()))
+            .collect()
+    }
+
     /// Returns the capabilities of resources.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn get_capabilities(&self) -> Result<resource::Capabilities> {
-        parse_response(self.api_request("capabilities", RequestMethod::Get, None)?)
+        self.parse_response(self.api_request("capabilities", RequestMethod::Get, None)?)
+    }
+
+    /// Returns the timezones that are supported by the bridge.
+    ///
+    /// These are the valid values for the [`timezone`] of the configuration.
+    ///
+    /// [`timezone`]: resource::config::Modifier::timezone
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn get_timezones(&self) -> Result<Vec<String>> {
+        let timezones: resource::capabilities::Timezones = self.parse_response(
+            self.api_request("capabilities/timezones", RequestMethod::Get, None)?,
+        )?;
+        Ok(timezones.values)
     }
 
     /// Creates a new schedule and returns the identifier.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn create_schedule(&self, creator: &resource::schedule::Creator) -> Result<String> {
         creator.execute(self)
     }
 
+    /// Creates a new schedule and returns it.
+    ///
+    /// This is the same as calling [`create_schedule`] followed by [`get_schedule`] with the
+    /// identifier of the new schedule.
+    ///
+    /// [`create_schedule`]: Self::create_schedule
+    /// [`get_schedule`]: Self::get_schedule
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn create_schedule_full(
+        &self,
+        creator: &resource::schedule::Creator,
+    ) -> Result<resource::Schedule> {
+        let id = self.create_schedule(creator)?;
+        self.get_schedule(id)
+    }
+
     /// Modifies attributes of a schedule.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn set_schedule<S>(
         &self,
         id: S,
@@ -341,16 +1946,23 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
     }
 
     /// Returns a schedule.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn get_schedule<S>(&self, id: S) -> Result<resource::Schedule>
     where
         S: Into<String>,
     {
         let id = id.into();
-        let schedule: resource::Schedule = parse_response(self.api_request(
+        record_id(&id);
+        let schedule: resource::Schedule = self.parse_response(self.api_request(
             format!("schedules/{}", id),
             RequestMethod::Get,
             None,
@@ -358,38 +1970,58 @@ impl Bridge {
         Ok(schedule.with_id(id))
     }
 
-    /// Returns all schedules.
+    /// Returns all schedules, sorted by their identifiers.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn get_all_schedules(&self) -> Result<Vec<resource::Schedule>> {
         let map: HashMap<String, resource::Schedule> =
-            parse_response(self.api_request("schedules", RequestMethod::Get, None)?)?;
-        Ok(map
-            .into_iter()
-            .map(|(id, schedule)| schedule.with_id(id))
-            .collect())
+            self.parse_response(self.api_request("schedules", RequestMethod::Get, None)?)?;
+        Ok(sorted_by_id(map, resource::Schedule::with_id))
     }
 
     /// Deletes a schedule.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn delete_schedule<S>(&self, id: S) -> Result<()>
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("schedules/{}", id.into()),
-            RequestMethod::Delete,
-            None,
-        )?;
+        let id = id.into();
+        record_id(&id);
+        let response: Vec<Response<JsonValue>> =
+            self.api_request(format!("schedules/{}", id), RequestMethod::Delete, None)?;
         for i in response {
             i.into_result()?;
         }
         Ok(())
     }
 
+    /// Deletes multiple schedules.
+    ///
+    /// The schedules are deleted one after another, even if deleting one of them fails. The results
+    /// are returned in the order of the identifiers.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn delete_schedules<S>(&self, ids: &[S]) -> Vec<Result<()>>
+    where
+        S: AsRef<str>,
+    {
+        ids.iter()
+            .map(|id| self.delete_schedule(id.as_ref()))
+            .collect()
+    }
+
     /// Creates a new resourcelink and returns the identifier.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn create_resourcelink(&self, creator: &resource::resourcelink::Creator) -> Result<String> {
         creator.execute(self)
     }
 
     /// Modifies attributes of a resourcelink.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn set_resourcelink<S>(
         &self,
         id: S,
@@ -398,16 +2030,23 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
     }
 
     /// Returns a resourcelink.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn get_resourcelink<S>(&self, id: S) -> Result<resource::Resourcelink>
     where
         S: Into<String>,
     {
         let id = id.into();
-        let resourcelink: resource::Resourcelink = parse_response(self.api_request(
+        record_id(&id);
+        let resourcelink: resource::Resourcelink = self.parse_response(self.api_request(
             format!("resourcelinks/{}", id),
             RequestMethod::Get,
             None,
@@ -415,33 +2054,44 @@ impl Bridge {
         Ok(resourcelink.with_id(id))
     }
 
-    /// Returns all resourcelinks.
+    /// Returns all resourcelinks, sorted by their identifiers.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn get_all_resourcelinks(&self) -> Result<Vec<resource::Resourcelink>> {
         let map: HashMap<String, resource::Resourcelink> =
-            parse_response(self.api_request("resourcelinks", RequestMethod::Get, None)?)?;
-        Ok(map
-            .into_iter()
-            .map(|(id, resourcelink)| resourcelink.with_id(id))
-            .collect())
+            self.parse_response(self.api_request("resourcelinks", RequestMethod::Get, None)?)?;
+        Ok(sorted_by_id(map, resource::Resourcelink::with_id))
     }
 
     /// Deletes a resourcelink.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn delete_resourcelink<S>(&self, id: S) -> Result<()>
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("resourcelinks/{}", id.into()),
-            RequestMethod::Delete,
-            None,
-        )?;
+        let id = id.into();
+        record_id(&id);
+        let response: Vec<Response<JsonValue>> =
+            self.api_request(format!("resourcelinks/{}", id), RequestMethod::Delete, None)?;
         for i in response {
             i.into_result()?;
         }
         Ok(())
     }
 
+    /// Creates a new CLIP sensor.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn create_sensor(&self, creator: &resource::sensor::Creator) -> Result<String> {
+        creator.execute(self)
+    }
+
     /// Modifies attributes of a sensor.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn set_sensor_attribute<S>(
         &self,
         id: S,
@@ -450,10 +2100,16 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
     }
 
     /// Modifies the state of a sensor.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn set_sensor_state<S>(
         &self,
         id: S,
@@ -462,10 +2118,16 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
     }
 
     /// Modifies the configuration of a sensor.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn set_sensor_config<S>(
         &self,
         id: S,
@@ -474,16 +2136,23 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
     }
 
     /// Returns a sensor.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn get_sensor<S>(&self, id: S) -> Result<resource::Sensor>
     where
         S: Into<String>,
     {
         let id = id.into();
-        let sensor: resource::Sensor = parse_response(self.api_request(
+        record_id(&id);
+        let sensor: resource::Sensor = self.parse_response(self.api_request(
             format!("sensors/{}", id),
             RequestMethod::Get,
             None,
@@ -491,16 +2160,60 @@ impl Bridge {
         Ok(sensor.with_id(id))
     }
 
-    /// Returns all sensors that are connected to the bridge.
+    /// Returns all sensors that are connected to the bridge, sorted by their identifiers.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn get_all_sensors(&self) -> Result<Vec<resource::Sensor>> {
         let map: HashMap<String, resource::Sensor> =
-            parse_response(self.api_request("sensors", RequestMethod::Get, None)?)?;
-        Ok(map
+            self.parse_response(self.api_request("sensors", RequestMethod::Get, None)?)?;
+        Ok(sorted_by_id(map, resource::Sensor::with_id))
+    }
+
+    /// Returns all motion sensors that report presence, sorted by their identifiers.
+    ///
+    /// The presence of a sensor is available in [`State::presence`]. The bridge does not link
+    /// sensors to rooms, so to get the occupancy of a room the sensors have to be mapped to it,
+    /// for example by their names.
+    ///
+    /// [`State::presence`]: resource::sensor::State::presence
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn presence_sensors(&self) -> Result<Vec<resource::Sensor>> {
+        Ok(self
+            .get_all_sensors()?
             .into_iter()
-            .map(|(id, sensor)| sensor.with_id(id))
+            .filter(resource::Sensor::is_presence_sensor)
             .collect())
     }
 
+    /// Returns the sensor with the given name.
+    ///
+    /// If no sensor has exactly the given name, the name is compared case-insensitively. If
+    /// multiple sensors have the same name, the first one that is returned by the bridge is used.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn get_sensor_by_name<S>(&self, name: S) -> Result<Option<resource::Sensor>>
+    where
+        S: AsRef<str>,
+    {
+        Ok(find_by_name(self.get_all_sensors()?, name.as_ref(), |v| {
+            &v.name
+        }))
+    }
+
+    /// Calls `f` for every sensor that is connected to the bridge.
+    ///
+    /// In comparison to [`get_all_sensors`], the sensors are deserialized one at a time while the
+    /// response is read, so they never have to be held in memory all at once.
+    ///
+    /// [`get_all_sensors`]: Self::get_all_sensors
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn for_each_sensor<F>(&self, mut f: F) -> Result<()>
+    where
+        F: FnMut(resource::Sensor),
+    {
+        self.for_each_resource("sensors", |id, sensor: resource::Sensor| {
+            f(sensor.with_id(id))
+        })
+    }
+
     /// Starts searching for new sensors.
     ///
     /// The bridge will open the network for 40 seconds. The overall search might take longer since
@@ -513,37 +2226,90 @@ impl Bridge {
     /// function.
     ///
     /// [`get_new_sensors`]: #method.get_new_sensors
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn search_new_sensors(&self, scanner: &resource::sensor::Scanner) -> Result<()> {
         scanner.execute(self)
     }
 
     /// Returns discovered sensors.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn get_new_sensors(&self) -> Result<resource::Scan> {
-        parse_response(self.api_request("senors/new", RequestMethod::Get, None)?)
+        self.parse_response(self.api_request("senors/new", RequestMethod::Get, None)?)
     }
 
     /// Deletes a sensor from the bridge.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn delete_sensor<S>(&self, id: S) -> Result<()>
     where
         S: Into<String>,
     {
-        let response: Vec<Response<JsonValue>> = self.api_request(
-            &format!("sensors/{}", id.into()),
-            RequestMethod::Delete,
-            None,
-        )?;
+        let id = id.into();
+        record_id(&id);
+        let response: Vec<Response<JsonValue>> =
+            self.api_request(format!("sensors/{}", id), RequestMethod::Delete, None)?;
         for i in response {
             i.into_result()?;
         }
         Ok(())
     }
 
+    /// Deletes multiple sensors.
+    ///
+    /// The sensors are deleted one after another, even if deleting one of them fails. The results
+    /// are returned in the order of the identifiers.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn delete_sensors<S>(&self, ids: &[S]) -> Vec<Result<()>>
+    where
+        S: AsRef<str>,
+    {
+        ids.iter()
+            .map(|id| self.delete_sensor(id.as_ref()))
+            .collect()
+    }
+
     /// Creates a new rule.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn create_rule(&self, creator: &resource::rule::Creator) -> Result<String> {
         creator.execute(self)
     }
 
+    /// Creates a new rule after checking that its conditions and actions reference existing
+    /// resources.
+    ///
+    /// The bridge accepts rules with addresses of resources that do not exist, but such rules are
+    /// never triggered. This requests the resources that are referenced by the addresses (e.g.
+    /// all sensors for `/sensors/2/state/buttonevent`) and returns
+    /// [`Error::UnresolvedAddresses`] with the addresses that do not reference an existing
+    /// resource. Addresses that do not reference a resource, like `/config/localtime`, are not
+    /// checked.
+    ///
+    /// [`Error::UnresolvedAddresses`]: crate::Error::UnresolvedAddresses
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn create_rule_checked(&self, creator: &resource::rule::Creator) -> Result<String> {
+        let addresses = creator
+            .conditions
+            .iter()
+            .map(|v| v.address.as_str())
+            .chain(creator.actions.iter().map(|v| v.address.as_str()));
+        let unresolved = unresolved_addresses(addresses, |kind| {
+            let map: HashMap<String, JsonValue> =
+                self.parse_response(self.api_request(kind, RequestMethod::Get, None)?)?;
+            Ok(map.into_keys().collect())
+        })?;
+        if !unresolved.is_empty() {
+            return Err(crate::Error::UnresolvedAddresses(unresolved));
+        }
+        creator.execute(self)
+    }
+
     /// Modifies attributes of a rule.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn set_rule<S>(
         &self,
         id: S,
@@ -552,37 +2318,469 @@ impl Bridge {
     where
         S: Into<String>,
     {
-        modifier.execute(self, id.into())
+        let id = id.into();
+        record_id(&id);
+        modifier.execute(self, id)
     }
 
     /// Returns a rule.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn get_rule<S>(&self, id: S) -> Result<resource::Rule>
     where
         S: Into<String>,
     {
         let id = id.into();
-        let rule: resource::Rule =
-            parse_response(self.api_request(format!("rules/{}", id), RequestMethod::Get, None)?)?;
+        record_id(&id);
+        let rule: resource::Rule = self.parse_response(self.api_request(
+            format!("rules/{}", id),
+            RequestMethod::Get,
+            None,
+        )?)?;
         Ok(rule.with_id(id))
     }
 
-    /// Returns all rules.
+    /// Returns all rules, sorted by their identifiers.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
     pub fn get_all_rules(&self) -> Result<Vec<resource::Rule>> {
         let map: HashMap<String, resource::Rule> =
-            parse_response(self.api_request("rules", RequestMethod::Get, None)?)?;
-        Ok(map.into_iter().map(|(id, rule)| rule.with_id(id)).collect())
+            self.parse_response(self.api_request("rules", RequestMethod::Get, None)?)?;
+        Ok(sorted_by_id(map, resource::Rule::with_id))
     }
 
     /// Deletes a rule.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(id = tracing::field::Empty), err)
+    )]
     pub fn delete_rule<S>(&self, id: S) -> Result<()>
     where
         S: Into<String>,
     {
+        let id = id.into();
+        record_id(&id);
         let response: Vec<Response<JsonValue>> =
-            self.api_request(&format!("rules/{}", id.into()), RequestMethod::Delete, None)?;
+            self.api_request(format!("rules/{}", id), RequestMethod::Delete, None)?;
         for i in response {
             i.into_result()?;
         }
         Ok(())
     }
+
+    /// Deletes multiple rules.
+    ///
+    /// The rules are deleted one after another, even if deleting one of them fails. The results
+    /// are returned in the order of the identifiers.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn delete_rules<S>(&self, ids: &[S]) -> Vec<Result<()>>
+    where
+        S: AsRef<str>,
+    {
+        ids.iter().map(|id| self.delete_rule(id.as_ref())).collect()
+    }
+
+    /// Returns the full datastore of the bridge, which can be restored with [`import_resources`].
+    ///
+    /// The datastore does not contain the light states of scenes, so every scene is requested
+    /// additionally and its light states are inserted into the returned document.
+    ///
+    /// [`import_resources`]: Self::import_resources
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn export_config(&self) -> Result<JsonValue> {
+        let mut datastore = self.get_raw("")?;
+        if let Some(scenes) = datastore
+            .get_mut("scenes")
+            .and_then(JsonValue::as_object_mut)
+        {
+            for (id, scene) in scenes.iter_mut() {
+                let full_scene = self.get_raw(format!("scenes/{}", id))?;
+                if let Some(light_states) = full_scene.get("lightstates") {
+                    scene["lightstates"] = light_states.clone();
+                }
+            }
+        }
+        Ok(datastore)
+    }
+
+    /// Recreates the groups, scenes, schedules, rules and resourcelinks of a document that was
+    /// returned by [`export_config`].
+    ///
+    /// The bridge has no API to restore a backup, so every resource is created again and gets a
+    /// new identifier. References to the imported resources in scenes, schedule commands, rules
+    /// and resourcelinks are rewritten to the new identifiers, and the username in schedule
+    /// commands is replaced by the username of this bridge. The following is not remapped:
+    ///
+    /// - Lights and sensors are not created, so their identifiers must be the same as in the
+    ///   document, for example when restoring to the same bridge.
+    /// - Resources are created in the order groups, scenes, schedules, rules and resourcelinks.
+    ///   A reference to a resource of a kind that is created later (e.g. a schedule that enables
+    ///   a rule) keeps the old identifier.
+    /// - Groups that are created by the bridge, like luminaires, are skipped.
+    /// - Scenes without light states store the current state of their lights.
+    ///
+    /// If the bridge rejects a resource, the import stops and [`Error::Import`] is returned. The
+    /// resources that were already created are kept and their identifiers are contained in the
+    /// error.
+    ///
+    /// [`export_config`]: Self::export_config
+    /// [`Error::Import`]: crate::Error::Import
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn import_resources(&self, document: &JsonValue) -> Result<ImportedIds> {
+        let mut ids = ImportedIds::default();
+        match self.import_resources_into(document, &mut ids) {
+            Ok(()) => Ok(ids),
+            Err(e) => Err(crate::Error::Import {
+                imported: Box::new(ids),
+                source: Box::new(e),
+            }),
+        }
+    }
+
+    /// Creates the resources of a document and inserts their identifiers into `ids`.
+    fn import_resources_into(&self, document: &JsonValue, ids: &mut ImportedIds) -> Result<()> {
+        for kind in backup::IMPORT_ORDER {
+            let resources = match document.get(kind).and_then(JsonValue::as_object) {
+                Some(v) => v,
+                None => continue,
+            };
+            for (old_id, resource) in resources {
+                let body = match ids.creation_body(kind, resource, &self.username) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                let mut response: Vec<Response<CreatedId>> =
+                    self.api_request(kind, RequestMethod::Post, Some(body))?;
+                let new_id = match response.pop() {
+                    Some(v) => v.into_result()?.id,
+                    None => return Err(crate::Error::GetCreatedId),
+                };
+                ids.insert(kind, old_id.clone(), new_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the resources that reference the resource with the given path (e.g. `/lights/5`).
+    ///
+    /// Scenes, the conditions and actions of rules, the commands of schedules and resourcelinks
+    /// are searched. This is useful to check what is affected before deleting a resource.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, err))]
+    pub fn find_references<S>(&self, resource_path: S) -> Result<References>
+    where
+        S: AsRef<str>,
+    {
+        Ok(References::find(
+            resource_path.as_ref(),
+            &self.get_all_scenes()?,
+            &self.get_all_rules()?,
+            &self.get_all_schedules()?,
+            &self.get_all_resourcelinks()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn sorted_by_id() {
+        let map: HashMap<String, String> = ["10", "9", "abc", "1", "Abc"]
+            .iter()
+            .map(|v| (v.to_string(), String::new()))
+            .collect();
+        let ids = super::sorted_by_id(map, |_, id| id);
+        assert_eq!(ids, vec!["1", "9", "10", "Abc", "abc"]);
+    }
+
+    #[test]
+    fn api_url() {
+        let ip = IpAddr::V4(std::net::Ipv4Addr::new(192, 168, 1, 2));
+        let bridge = Bridge::new(ip, "user");
+        assert_eq!(bridge.api_url, "http://192.168.1.2:80/api/user");
+        let bridge = Bridge::new_with_port(ip, 8080, "user");
+        assert_eq!(bridge.api_url, "http://192.168.1.2:8080/api/user");
+        assert_eq!(bridge.port(), 8080);
+        assert_eq!(bridge.base_url(), "http://192.168.1.2:8080");
+        let bridge = Bridge::new_with_port(IpAddr::V6(std::net::Ipv6Addr::LOCALHOST), 80, "user");
+        assert_eq!(bridge.api_url, "http://[::1]:80/api/user");
+        let bridge = Bridge::new(ip, "user").with_base_url("https://example.com/hue/proxy/");
+        assert_eq!(bridge.base_url(), "https://example.com/hue/proxy");
+        assert_eq!(bridge.api_url, "https://example.com/hue/proxy/api/user");
+    }
+
+    /// Returns a bridge whose requests fail without connecting, because no address is resolved.
+    fn unreachable_bridge() -> Bridge {
+        let agent = ureq::AgentBuilder::new()
+            .resolver(|_: &str| -> std::io::Result<Vec<SocketAddr>> { Ok(Vec::new()) })
+            .build();
+        let ip = IpAddr::V4(std::net::Ipv4Addr::LOCALHOST);
+        Bridge {
+            agent: HttpAgent {
+                agent,
+                proxy_url: None,
+            },
+            ..Bridge::new(ip, "user")
+        }
+    }
+
+    #[test]
+    fn reachable() {
+        assert!(!unreachable_bridge().reachable().unwrap());
+    }
+
+    #[test]
+    fn unused_users() {
+        let json = json!({
+            "name": "test",
+            "swversion": "1940094000",
+            "apiversion": "1.40.0",
+            "linkbutton": false,
+            "ipaddress": "192.168.1.2",
+            "mac": "00:17:88:00:00:00",
+            "netmask": "255.255.255.0",
+            "gateway": "192.168.1.1",
+            "dhcp": true,
+            "portalservices": true,
+            "UTC": "2020-07-01T00:00:00",
+            "modelid": "BSB002",
+            "bridgeid": "001788FFFE000000",
+            "whitelist": {
+                "current": {
+                    "name": "current",
+                    "last use date": "2019-01-01T00:00:00",
+                    "create date": "2019-01-01T00:00:00"
+                },
+                "stale": {
+                    "name": "stale",
+                    "last use date": "2020-01-01T00:00:00",
+                    "create date": "2019-01-01T00:00:00"
+                },
+                "recent": {
+                    "name": "recent",
+                    "last use date": "2020-06-30T00:00:00",
+                    "create date": "2019-01-01T00:00:00"
+                }
+            }
+        });
+        let config: resource::Config = serde_json::from_value(json).unwrap();
+        let usernames = super::unused_users(&config, chrono::Duration::days(30), "current");
+        assert_eq!(usernames, vec!["stale"]);
+        let usernames = super::unused_users(&config, chrono::Duration::days(365), "current");
+        assert!(usernames.is_empty());
+    }
+
+    #[test]
+    fn delete_users() {
+        let bridge = unreachable_bridge();
+        let results = bridge.delete_users(vec!["stale".into(), "other".into()]);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "stale");
+        assert!(matches!(results[0].1, Err(crate::Error::Connection(_))));
+        assert_eq!(results[1].0, "other");
+        assert!(results[1].1.is_err());
+    }
+
+    #[test]
+    fn proxy() {
+        let ip = IpAddr::V4(std::net::Ipv4Addr::new(192, 168, 1, 2));
+        let bridge = Bridge::new(ip, "user");
+        assert_eq!(bridge.proxy(), None);
+        let bridge = bridge.with_proxy("http://localhost:8080").unwrap();
+        assert_eq!(bridge.proxy(), Some("http://localhost:8080"));
+        assert_eq!(bridge, bridge.clone());
+        assert!(Bridge::new(ip, "user")
+            .with_proxy("ftp://localhost")
+            .is_err());
+    }
+
+    #[test]
+    fn unresolved_addresses() {
+        let mut requested = Vec::new();
+        let addresses = vec![
+            "/sensors/2/state/buttonevent",
+            "/sensors/3/state/presence",
+            "/config/localtime",
+            "/groups/0/action",
+            "/lights/1/state",
+            "/sensors/3/state/presence",
+        ];
+        let unresolved = super::unresolved_addresses(addresses, |kind| {
+            requested.push(kind.to_owned());
+            Ok(match kind {
+                "sensors" => vec!["1".into(), "2".into()],
+                _ => vec!["1".into()],
+            })
+        })
+        .unwrap();
+        assert_eq!(unresolved, vec!["/sensors/3/state/presence"]);
+        assert_eq!(requested, vec!["sensors", "lights"]);
+    }
+
+    #[test]
+    fn http_request() {
+        let request = super::http_request(RequestMethod::Put, "http://127.0.0.1/api");
+        assert_eq!(request.method(), "PUT");
+        assert_eq!(request.header("User-Agent"), Some(USER_AGENT));
+        assert!(USER_AGENT.starts_with("huelib-rs/"));
+    }
+
+    #[test]
+    fn find_by_name() {
+        let names = vec!["Kitchen", "kitchen", "Office"];
+        assert_eq!(
+            super::find_by_name(names.clone(), "kitchen", |v| v),
+            Some("kitchen")
+        );
+        assert_eq!(
+            super::find_by_name(names.clone(), "office", |v| v),
+            Some("Office")
+        );
+        assert_eq!(super::find_by_name(names, "Bedroom", |v| v), None);
+    }
+
+    #[test]
+    fn parse_response() {
+        let json = json!({"name": "test"});
+        let value: JsonValue = super::parse_response(json.clone()).unwrap();
+        assert_eq!(value, json);
+
+        let json = json!([{"success": "test"}]);
+        let value: JsonValue = super::parse_response(json.clone()).unwrap();
+        assert_eq!(value, json);
+
+        let json = json!([{"error": {"type": 3, "address": "/lights/1", "description": "test"}}]);
+        match super::parse_response::<JsonValue>(json) {
+            Err(crate::Error::Response(e)) => {
+                assert_eq!(e.kind, crate::response::ErrorKind::ResourceNotAvailable)
+            }
+            v => panic!("unexpected result: {:?}", v),
+        }
+    }
+
+    #[test]
+    fn parse_response_collecting() {
+        #[derive(Debug, Deserialize)]
+        struct State {
+            #[allow(dead_code)]
+            on: bool,
+        }
+        let json = json!({"1": {"on": true, "new": 1}, "2": {"on": false}});
+        let mut warnings = Vec::new();
+        let _: HashMap<String, State> =
+            super::parse_response_collecting(json, Some(&mut warnings)).unwrap();
+        assert_eq!(warnings, vec![Warning::UnknownAttribute("1.new".into())]);
+    }
+
+    fn for_each(json: &str) -> Result<Vec<(String, u8)>> {
+        let mut values = Vec::new();
+        let visitor = ForEachVisitor {
+            f: |id, value| values.push((id, value)),
+            marker: PhantomData,
+        };
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        de::Deserializer::deserialize_any(&mut deserializer, visitor)??;
+        deserializer.end()?;
+        Ok(values)
+    }
+
+    #[test]
+    fn for_each_visitor() {
+        let values = for_each(r#"{"1": 10, "2": 20}"#).unwrap();
+        assert_eq!(values, vec![("1".to_owned(), 10), ("2".to_owned(), 20)]);
+
+        let json = r#"[{"error": {"type": 1, "address": "/lights", "description": "test"}}]"#;
+        assert!(matches!(for_each(json), Err(crate::Error::Response(_))));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_span_id() {
+        use std::sync::Mutex;
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+
+        type Spans = Arc<Mutex<Vec<(&'static tracing::Metadata<'static>, Vec<(String, String)>)>>>;
+
+        struct Fields<'a>(&'a mut Vec<(String, String)>);
+
+        impl Visit for Fields<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+                self.0
+                    .push((field.name().to_owned(), format!("{:?}", value)));
+            }
+
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.0.push((field.name().to_owned(), value.to_owned()));
+            }
+        }
+
+        /// Subscriber that records the fields of all spans.
+        struct Recorder {
+            spans: Spans,
+            entered: Mutex<Vec<Id>>,
+        }
+
+        impl tracing::Subscriber for Recorder {
+            fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+                let mut spans = self.spans.lock().unwrap();
+                let mut fields = Vec::new();
+                attributes.record(&mut Fields(&mut fields));
+                spans.push((attributes.metadata(), fields));
+                Id::from_u64(spans.len() as u64)
+            }
+
+            fn record(&self, id: &Id, values: &Record<'_>) {
+                let mut spans = self.spans.lock().unwrap();
+                let index = id.into_u64() as usize - 1;
+                values.record(&mut Fields(&mut spans[index].1));
+            }
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+            fn event(&self, _: &tracing::Event<'_>) {}
+
+            fn enter(&self, id: &Id) {
+                self.entered.lock().unwrap().push(id.clone());
+            }
+
+            fn exit(&self, _: &Id) {
+                self.entered.lock().unwrap().pop();
+            }
+
+            fn current_span(&self) -> tracing_core::span::Current {
+                match self.entered.lock().unwrap().last() {
+                    Some(id) => {
+                        let index = id.into_u64() as usize - 1;
+                        let metadata = self.spans.lock().unwrap()[index].0;
+                        tracing_core::span::Current::new(id.clone(), metadata)
+                    }
+                    None => tracing_core::span::Current::none(),
+                }
+            }
+        }
+
+        let spans = Spans::default();
+        let bridge = unreachable_bridge();
+        let recorder = Recorder {
+            spans: spans.clone(),
+            entered: Mutex::default(),
+        };
+        tracing::subscriber::with_default(recorder, || {
+            let _ = bridge.get_light("5");
+        });
+        let spans = spans.lock().unwrap();
+        let (_, fields) = spans
+            .iter()
+            .find(|(metadata, _)| metadata.name() == "get_light")
+            .unwrap();
+        assert!(fields.contains(&("id".to_owned(), "5".to_owned())));
+    }
 }
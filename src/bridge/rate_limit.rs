@@ -0,0 +1,76 @@
+use super::shared::Shared;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Token bucket that limits the number of requests per second.
+///
+/// The bucket holds at most as many tokens as requests are allowed per second, so short bursts
+/// are sent immediately while longer bursts are paced.
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RateLimiter {
+    per_second: u32,
+    state: Shared<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(per_second: u32) -> Self {
+        Self {
+            per_second,
+            state: Shared::new(State {
+                tokens: per_second.into(),
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    pub(crate) fn per_second(&self) -> u32 {
+        self.per_second
+    }
+
+    /// Blocks until a request can be sent.
+    pub(crate) fn acquire(&self) {
+        let wait = self.reserve(Instant::now());
+        if wait > Duration::from_secs(0) {
+            thread::sleep(wait);
+        }
+    }
+
+    /// Takes a token and returns how long to wait until the token is available.
+    fn reserve(&self, now: Instant) -> Duration {
+        let rate = f64::from(self.per_second);
+        let mut state = self.state.lock();
+        let elapsed = now.saturating_duration_since(state.last_refill);
+        state.tokens = (state.tokens + elapsed.as_secs_f64() * rate).min(rate);
+        state.last_refill = now;
+        state.tokens -= 1.0;
+        if state.tokens < 0.0 {
+            Duration::from_secs_f64(-state.tokens / rate)
+        } else {
+            Duration::from_secs(0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserve() {
+        let rate_limiter = RateLimiter::new(10);
+        let now = Instant::now();
+        for _ in 0..10 {
+            assert_eq!(rate_limiter.reserve(now), Duration::from_secs(0));
+        }
+        assert_eq!(rate_limiter.reserve(now), Duration::from_millis(100));
+        assert_eq!(rate_limiter.reserve(now), Duration::from_millis(200));
+        let now = now + Duration::from_secs(1);
+        assert_eq!(rate_limiter.reserve(now), Duration::from_secs(0));
+    }
+}
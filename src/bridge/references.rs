@@ -0,0 +1,205 @@
+use crate::resource::{Resourcelink, Rule, Scene, Schedule};
+use serde_json::Value as JsonValue;
+
+/// Identifiers of the resources that reference another resource, grouped by kind.
+///
+/// Returned by [`Bridge::find_references`].
+///
+/// [`Bridge::find_references`]: crate::Bridge::find_references
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct References {
+    /// Identifiers of scenes that contain the resource.
+    pub scenes: Vec<String>,
+    /// Identifiers of rules with a condition or action that references the resource.
+    pub rules: Vec<String>,
+    /// Identifiers of schedules with a command that references the resource.
+    pub schedules: Vec<String>,
+    /// Identifiers of resourcelinks that link to the resource.
+    pub resourcelinks: Vec<String>,
+}
+
+impl References {
+    /// Returns whether no resource references the resource.
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+            && self.rules.is_empty()
+            && self.schedules.is_empty()
+            && self.resourcelinks.is_empty()
+    }
+
+    pub(crate) fn find(
+        resource_path: &str,
+        scenes: &[Scene],
+        rules: &[Rule],
+        schedules: &[Schedule],
+        resourcelinks: &[Resourcelink],
+    ) -> Self {
+        let path = normalize_path(resource_path);
+        Self {
+            scenes: filter_ids(scenes, |v| &v.id, |v| scene_references(v, &path)),
+            rules: filter_ids(rules, |v| &v.id, |v| rule_references(v, &path)),
+            schedules: filter_ids(schedules, |v| &v.id, |v| schedule_references(v, &path)),
+            resourcelinks: filter_ids(
+                resourcelinks,
+                |v| &v.id,
+                |v| v.links.iter().any(|link| link.to_string() == path),
+            ),
+        }
+    }
+}
+
+fn filter_ids<T>(
+    values: &[T],
+    id: impl Fn(&T) -> &String,
+    predicate: impl Fn(&T) -> bool,
+) -> Vec<String> {
+    let mut ids: Vec<String> = values
+        .iter()
+        .filter(|v| predicate(v))
+        .map(|v| id(v).clone())
+        .collect();
+    ids.sort_by(|a, b| super::compare_ids(a, b));
+    ids
+}
+
+/// Converts a path like `lights/5/` into `/lights/5`.
+fn normalize_path(path: &str) -> String {
+    format!("/{}", path.trim_matches('/'))
+}
+
+fn scene_references(scene: &Scene, path: &str) -> bool {
+    let mut segments = path[1..].splitn(2, '/');
+    match (segments.next(), segments.next()) {
+        (Some("lights"), Some(id)) => {
+            scene.lights.iter().flatten().any(|v| v == id)
+                || scene.light_states.iter().any(|v| v.contains_key(id))
+        }
+        (Some("groups"), Some(id)) => scene.group.as_deref() == Some(id),
+        _ => false,
+    }
+}
+
+fn rule_references(rule: &Rule, path: &str) -> bool {
+    rule.conditions
+        .iter()
+        .any(|v| address_references(&v.address, path))
+        || rule
+            .actions
+            .iter()
+            .any(|v| address_references(&v.address, path) || body_references(&v.body, path))
+}
+
+fn schedule_references(schedule: &Schedule, path: &str) -> bool {
+    let address = strip_api_prefix(&schedule.command.address);
+    address_references(address, path) || body_references(&schedule.command.body, path)
+}
+
+/// Returns whether the address is the path or a sub-path of it.
+fn address_references(address: &str, path: &str) -> bool {
+    address.starts_with(path) && matches!(address.as_bytes().get(path.len()), None | Some(b'/'))
+}
+
+/// Returns whether the body recalls the scene with the path.
+fn body_references(body: &JsonValue, path: &str) -> bool {
+    match path.strip_prefix("/scenes/") {
+        Some(id) => body.get("scene").and_then(JsonValue::as_str) == Some(id),
+        None => false,
+    }
+}
+
+/// Removes the `/api/<username>` prefix from the address of a schedule command.
+fn strip_api_prefix(address: &str) -> &str {
+    match address.strip_prefix("/api/") {
+        Some(v) => v.find('/').map_or("", |i| &v[i..]),
+        None => address,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn find_references() {
+        let mut scene: Scene = serde_json::from_value(json!({
+            "name": "test",
+            "type": "GroupScene",
+            "group": "1",
+            "lights": ["5", "6"],
+            "version": 2
+        }))
+        .unwrap();
+        scene.id = "abc".into();
+        let mut rule: Rule = serde_json::from_value(json!({
+            "name": "test",
+            "owner": "user",
+            "created": "2020-01-01T00:00:00",
+            "lasttriggered": "none",
+            "timestriggered": 0,
+            "status": "enabled",
+            "conditions": [{"address": "/sensors/2/state/buttonevent", "operator": "dx"}],
+            "actions": [
+                {"address": "/groups/0/action", "method": "PUT", "body": {"scene": "abc"}}
+            ]
+        }))
+        .unwrap();
+        rule.id = "3".into();
+        let mut schedule: Schedule = serde_json::from_value(json!({
+            "name": "test",
+            "description": "",
+            "command": {"address": "/api/user/lights/5/state", "method": "PUT", "body": {}},
+            "localtime": "W127/T07:00:00",
+            "time": "W127/T07:00:00",
+            "created": "2020-01-01T00:00:00",
+            "status": "enabled"
+        }))
+        .unwrap();
+        schedule.id = "4".into();
+        let mut resourcelink: Resourcelink = serde_json::from_value(json!({
+            "name": "test",
+            "description": "",
+            "owner": "user",
+            "type": "Link",
+            "classid": 1,
+            "recycle": false,
+            "links": ["/scenes/abc", "/sensors/2"]
+        }))
+        .unwrap();
+        resourcelink.id = "7".into();
+
+        let find = |path| {
+            References::find(
+                path,
+                &[scene.clone()],
+                &[rule.clone()],
+                &[schedule.clone()],
+                &[resourcelink.clone()],
+            )
+        };
+        let references = find("/lights/5");
+        assert_eq!(references.scenes, vec!["abc"]);
+        assert!(references.rules.is_empty());
+        assert_eq!(references.schedules, vec!["4"]);
+        assert!(references.resourcelinks.is_empty());
+
+        let references = find("scenes/abc/");
+        assert!(references.scenes.is_empty());
+        assert_eq!(references.rules, vec!["3"]);
+        assert_eq!(references.resourcelinks, vec!["7"]);
+
+        let references = find("/sensors/2");
+        assert_eq!(references.rules, vec!["3"]);
+        assert_eq!(references.resourcelinks, vec!["7"]);
+
+        assert!(find("/lights/50").is_empty());
+        assert!(find("/sensors/20").is_empty());
+
+        let other_rule = Rule {
+            id: "10".into(),
+            ..rule.clone()
+        };
+        let references = References::find("/sensors/2", &[], &[other_rule, rule], &[], &[]);
+        assert_eq!(references.rules, vec!["3", "10"]);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::resource::RequestMethod;
 use crate::{Error, Response, Result};
 use serde::Deserialize;
 use std::net::IpAddr;
@@ -7,6 +8,11 @@ use std::net::IpAddr;
 /// This function returns the new username. See the [`register_user_with_clientkey`] function if you
 /// also want to generate a clientkey.
 ///
+/// The request is sent to `http://<ip address>/api`. Use [`Bridge::ensure_authorized`] for a
+/// bridge with another port or base URL.
+///
+/// [`Bridge::ensure_authorized`]: crate::Bridge::ensure_authorized
+///
 /// # Examples
 ///
 /// Register a user and print the username:
@@ -26,8 +32,16 @@ where
     S: AsRef<str>,
 {
     let url = format!("http://{}/api", ip_address);
-    let body = format!("{{\"devicetype\":\"{}\"}}", devicetype.as_ref());
-    let http_response = ureq::post(&url).send_string(&body)?;
+    register_user_at(
+        super::http_request(RequestMethod::Post, &url),
+        devicetype.as_ref(),
+    )
+}
+
+/// Registers a new user by sending the given POST request to the Philips Hue API.
+pub(crate) fn register_user_at(request: ureq::Request, devicetype: &str) -> Result<String> {
+    let body = serde_json::json!({ "devicetype": devicetype });
+    let http_response = super::send_json(request, body)?;
     #[derive(Deserialize)]
     struct User {
         username: String,
@@ -42,12 +56,40 @@ where
     }
 }
 
+/// Decodes a clientkey that was returned by [`register_user_with_clientkey`] into its 16 bytes.
+///
+/// The bytes are used as pre-shared key of the DTLS connection for entertainment streaming.
+/// Returns [`Error::InvalidClientKey`] if the clientkey is not a 16 byte hexadecimal encoded key.
+///
+/// # Examples
+///
+/// ```
+/// use huelib::bridge;
+///
+/// let key = bridge::decode_clientkey("000102030405060708090A0B0C0D0EFF").unwrap();
+/// assert_eq!(key[15], 255);
+/// ```
+pub fn decode_clientkey(clientkey: &str) -> Result<[u8; 16]> {
+    if clientkey.len() != 32 || !clientkey.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(Error::InvalidClientKey);
+    }
+    let mut key = [0; 16];
+    for (i, v) in key.iter_mut().enumerate() {
+        *v = u8::from_str_radix(&clientkey[i * 2..i * 2 + 2], 16)
+            .map_err(|_| Error::InvalidClientKey)?;
+    }
+    Ok(key)
+}
+
 /// Registers a new user on a bridge with a clientkey.
 ///
 /// This function returns the new username and a random generated 16 byte clientkey encoded as ASCII
 /// string of length 32. See the [`register_user`] function if you don't want to generate a
 /// clientkey.
 ///
+/// The request is sent to `http://<ip address>/api`, so bridges with another port or base URL are
+/// not supported.
+///
 /// # Examples
 ///
 /// Register a user and print the username and clientkey:
@@ -70,11 +112,11 @@ where
     S: AsRef<str>,
 {
     let url = format!("http://{}/api", ip_address);
-    let body = format!(
-        "{{\"devicetype\":\"{}\",\"generateclientkey\":true}}",
-        devicetype.as_ref()
-    );
-    let http_response = ureq::post(&url).send_string(&body)?;
+    let body = serde_json::json!({
+        "devicetype": devicetype.as_ref(),
+        "generateclientkey": true,
+    });
+    let http_response = super::send_json(super::http_request(RequestMethod::Post, &url), body)?;
     #[derive(Deserialize)]
     struct User {
         username: String,
@@ -89,3 +131,15 @@ where
         None => Err(Error::GetUsername),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn decode_clientkey() {
+        let key = super::decode_clientkey("000102030405060708090A0B0C0D0eff").unwrap();
+        assert_eq!(key, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 255]);
+        assert!(super::decode_clientkey("0001").is_err());
+        assert!(super::decode_clientkey("000102030405060708090A0B0C0D0EXX").is_err());
+        assert!(super::decode_clientkey("+00102030405060708090A0B0C0D0E0F").is_err());
+    }
+}
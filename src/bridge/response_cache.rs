@@ -0,0 +1,77 @@
+use super::shared::Shared;
+use crate::resource::Light;
+use crate::Result;
+
+/// Cache of parsed responses that is used to skip parsing when a response did not change.
+///
+/// Responses are identified by their body. The cache is cleared whenever a request that modifies
+/// the bridge is sent.
+#[derive(Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct ResponseCache {
+    lights: Shared<Option<Entry<Vec<Light>>>>,
+}
+
+#[derive(Debug)]
+struct Entry<T> {
+    body: String,
+    value: T,
+}
+
+impl ResponseCache {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached lights if the body is unchanged, otherwise parses and caches them.
+    pub(crate) fn lights<F>(&self, body: &str, parse: F) -> Result<Vec<Light>>
+    where
+        F: FnOnce(&str) -> Result<Vec<Light>>,
+    {
+        get_or_parse(&self.lights, body, parse)
+    }
+
+    /// Removes all cached responses.
+    pub(crate) fn clear(&self) {
+        *self.lights.lock() = None;
+    }
+}
+
+fn get_or_parse<T, F>(entry: &Shared<Option<Entry<T>>>, body: &str, parse: F) -> Result<T>
+where
+    T: Clone,
+    F: FnOnce(&str) -> Result<T>,
+{
+    if let Some(entry) = entry.lock().as_ref().filter(|v| v.body == body) {
+        return Ok(entry.value.clone());
+    }
+    let value = parse(body)?;
+    *entry.lock() = Some(Entry {
+        body: body.to_owned(),
+        value: value.clone(),
+    });
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn get_or_parse() {
+        let entry = Shared::new(None);
+        let calls = Cell::new(0);
+        let parse = |body: &str| {
+            calls.set(calls.get() + 1);
+            Ok(body.len())
+        };
+        assert_eq!(super::get_or_parse(&entry, "{}", parse).unwrap(), 2);
+        assert_eq!(super::get_or_parse(&entry, "{}", parse).unwrap(), 2);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(super::get_or_parse(&entry, "{\"1\":{}}", parse).unwrap(), 8);
+        assert_eq!(calls.get(), 2);
+        *entry.lock() = None;
+        assert_eq!(super::get_or_parse(&entry, "{\"1\":{}}", parse).unwrap(), 8);
+        assert_eq!(calls.get(), 3);
+    }
+}
@@ -0,0 +1,101 @@
+use super::shared::Shared;
+use crate::resource::{LastScan, Scan};
+use std::collections::HashSet;
+
+/// Identifiers of new resources that were already returned from a scan.
+#[derive(Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct SeenResources {
+    lights: Shared<Seen>,
+}
+
+/// Identifiers that were returned from the scan with the given status.
+#[derive(Debug, Default)]
+struct Seen {
+    last_scan: Option<LastScan>,
+    ids: HashSet<String>,
+}
+
+impl Seen {
+    /// Removes the resources that were already returned from the same scan and remembers the
+    /// others.
+    ///
+    /// The identifiers are forgotten when another scan started, which is the case if the status
+    /// changed and the previous scan was not active.
+    fn retain_unseen(&mut self, scan: &mut Scan) {
+        let is_new_scan = match &self.last_scan {
+            Some(LastScan::Active) | None => false,
+            Some(v) => *v != scan.last_scan,
+        };
+        if is_new_scan {
+            self.ids.clear();
+        }
+        self.last_scan = Some(scan.last_scan.clone());
+        let ids = &mut self.ids;
+        scan.resources.retain(|v| ids.insert(v.id.clone()));
+    }
+}
+
+impl SeenResources {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes the lights that were already returned from the same scan and remembers the others.
+    pub(crate) fn retain_unseen_lights(&self, scan: &mut Scan) {
+        self.lights.lock().retain_unseen(scan);
+    }
+
+    /// Forgets the lights that were returned.
+    pub(crate) fn reset_lights(&self) {
+        *self.lights.lock() = Seen::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::ScanResource;
+
+    #[test]
+    fn retain_unseen_lights() {
+        let scan = |last_scan: &LastScan, ids: &[&str]| Scan {
+            last_scan: last_scan.clone(),
+            resources: ids
+                .iter()
+                .map(|&id| ScanResource {
+                    id: id.to_owned(),
+                    name: format!("Light {}", id),
+                })
+                .collect(),
+        };
+        let finished = |time: &str| LastScan::DateTime(time.parse().unwrap());
+        let seen = SeenResources::new();
+        let mut value = scan(&LastScan::Active, &["1", "2"]);
+        seen.retain_unseen_lights(&mut value);
+        assert_eq!(value, scan(&LastScan::Active, &["1", "2"]));
+        let mut value = scan(&LastScan::Active, &["1", "2", "3"]);
+        seen.retain_unseen_lights(&mut value);
+        assert_eq!(value, scan(&LastScan::Active, &["3"]));
+        let last_scan = finished("2020-01-01T00:00:00");
+        let mut value = scan(&last_scan, &["1", "2", "3"]);
+        seen.retain_unseen_lights(&mut value);
+        assert_eq!(value, scan(&last_scan, &[]));
+
+        let mut value = scan(&LastScan::Active, &["3"]);
+        seen.retain_unseen_lights(&mut value);
+        assert_eq!(value, scan(&LastScan::Active, &["3"]));
+        let last_scan = finished("2020-01-02T00:00:00");
+        let mut value = scan(&last_scan, &["3"]);
+        seen.retain_unseen_lights(&mut value);
+        assert_eq!(value, scan(&last_scan, &[]));
+        let other_scan = finished("2020-01-03T00:00:00");
+        let mut value = scan(&other_scan, &["3"]);
+        seen.retain_unseen_lights(&mut value);
+        assert_eq!(value, scan(&other_scan, &["3"]));
+
+        seen.reset_lights();
+        let mut value = scan(&other_scan, &["3"]);
+        seen.retain_unseen_lights(&mut value);
+        assert_eq!(value, scan(&other_scan, &["3"]));
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{Bridge, Error, Result};
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+use std::{io, thread};
+
+/// Duration in which the link button has to be pressed when a new user is registered.
+const LINK_BUTTON_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Interval in which registering a user is retried while waiting for the link button.
+const LINK_BUTTON_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// IP address of a bridge and username of a registered user.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Credentials {
+    /// IP address of the bridge.
+    pub ip_address: IpAddr,
+    /// Username of the registered user.
+    pub username: String,
+}
+
+/// Trait for persisting the credentials of a [`Session`], for example in a file.
+pub trait CredentialStore {
+    /// Returns the stored credentials, or `None` if no credentials are stored yet.
+    fn load(&mut self) -> io::Result<Option<Credentials>>;
+
+    /// Stores the credentials, replacing previously stored credentials.
+    fn save(&mut self, credentials: &Credentials) -> io::Result<()>;
+}
+
+/// A bridge with an authorized user, whose credentials are persisted in a [`CredentialStore`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Session {
+    bridge: Bridge,
+}
+
+impl Session {
+    /// Connects to a bridge with the stored credentials, or discovers a bridge and registers a new
+    /// user.
+    ///
+    /// The steps are:
+    ///
+    /// 1. If credentials are stored and the bridge is reachable at the stored IP address, the
+    ///    stored user is used if it is still authorized.
+    /// 2. If the bridge is not reachable, bridges are discovered with [`discover_nupnp`] and the
+    ///    stored user is used with the first bridge that authorizes it, because the IP address of
+    ///    the bridge may have changed.
+    /// 3. Otherwise a new user is registered with the given device type, on the stored bridge if
+    ///    it is reachable (e.g. after a factory reset) or on the first discovered bridge. The
+    ///    link button of the bridge has to be pressed within 60 seconds.
+    ///
+    /// The credentials are saved whenever they change. Returns [`Error::BridgeNotFound`] if no
+    /// bridge is discovered, [`Error::CredentialStore`] if the store fails, or an error with
+    /// [`ErrorKind::LinkButtonNotPressed`] if the link button was not pressed in time.
+    ///
+    /// [`discover_nupnp`]: super::discover_nupnp
+    /// [`Error::BridgeNotFound`]: crate::Error::BridgeNotFound
+    /// [`Error::CredentialStore`]: crate::Error::CredentialStore
+    /// [`ErrorKind::LinkButtonNotPressed`]: crate::response::ErrorKind::LinkButtonNotPressed
+    pub fn connect_or_register<S, C>(devicetype: S, credential_store: &mut C) -> Result<Self>
+    where
+        S: AsRef<str>,
+        C: CredentialStore,
+    {
+        let devicetype = devicetype.as_ref();
+        let stored = credential_store
+            .load()
+            .map_err(Error::CredentialStore)?
+            .filter(|v| !v.username.is_empty());
+        let mut register_ip_address = None;
+        if let Some(credentials) = &stored {
+            let bridge = Bridge::try_new(credentials.ip_address, credentials.username.as_str())?;
+            if bridge.reachable()? {
+                if bridge.is_authorized()? {
+                    return Ok(Self { bridge });
+                }
+                log::info!("Stored user is not authorized anymore");
+                register_ip_address = Some(credentials.ip_address);
+            }
+        }
+        let ip_address = match register_ip_address {
+            Some(v) => v,
+            None => {
+                let ip_addresses = super::discover_nupnp()?;
+                if let Some(credentials) = &stored {
+                    for ip_address in ip_addresses.iter().copied() {
+                        let bridge = Bridge::try_new(ip_address, credentials.username.as_str())?;
+                        if bridge.reachable()? && bridge.is_authorized()? {
+                            let credentials = Credentials {
+                                ip_address,
+                                username: credentials.username.clone(),
+                            };
+                            return Self::save(bridge, &credentials, credential_store);
+                        }
+                    }
+                }
+                ip_addresses
+                    .into_iter()
+                    .next()
+                    .ok_or(Error::BridgeNotFound)?
+            }
+        };
+        let credentials = Credentials {
+            ip_address,
+            username: register_user_waiting(ip_address, devicetype)?,
+        };
+        let bridge = Bridge::try_new(ip_address, credentials.username.as_str())?;
+        Self::save(bridge, &credentials, credential_store)
+    }
+
+    fn save<C>(bridge: Bridge, credentials: &Credentials, credential_store: &mut C) -> Result<Self>
+    where
+        C: CredentialStore,
+    {
+        credential_store
+            .save(credentials)
+            .map_err(Error::CredentialStore)?;
+        Ok(Self { bridge })
+    }
+
+    /// Returns the bridge of the session.
+    pub fn bridge(&self) -> &Bridge {
+        &self.bridge
+    }
+
+    /// Converts the session into its bridge.
+    pub fn into_bridge(self) -> Bridge {
+        self.bridge
+    }
+}
+
+/// Registers a new user, retrying until the link button is pressed or the timeout elapsed.
+fn register_user_waiting(ip_address: IpAddr, devicetype: &str) -> Result<String> {
+    log::info!("Press the link button of the bridge at {}", ip_address);
+    let deadline = Instant::now() + LINK_BUTTON_TIMEOUT;
+    loop {
+        match super::register_user(ip_address, devicetype) {
+            Err(e) if e.is_link_button_not_pressed() && Instant::now() < deadline => {
+                thread::sleep(LINK_BUTTON_POLL_INTERVAL);
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    /// Credential store that keeps the credentials in memory and can be made to fail.
+    #[derive(Default)]
+    struct MemoryStore {
+        credentials: Option<Credentials>,
+        fail_load: bool,
+        fail_save: bool,
+    }
+
+    impl CredentialStore for MemoryStore {
+        fn load(&mut self) -> io::Result<Option<Credentials>> {
+            if self.fail_load {
+                return Err(io::Error::other("load failed"));
+            }
+            Ok(self.credentials.clone())
+        }
+
+        fn save(&mut self, credentials: &Credentials) -> io::Result<()> {
+            if self.fail_save {
+                return Err(io::Error::other("save failed"));
+            }
+            self.credentials = Some(credentials.clone());
+            Ok(())
+        }
+    }
+
+    fn credentials() -> Credentials {
+        Credentials {
+            ip_address: IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)),
+            username: "user".into(),
+        }
+    }
+
+    #[test]
+    fn connect_or_register_load_error() {
+        let mut store = MemoryStore {
+            fail_load: true,
+            ..Default::default()
+        };
+        let result = Session::connect_or_register("huelib#test", &mut store);
+        assert!(matches!(result, Err(Error::CredentialStore(_))));
+    }
+
+    #[test]
+    fn save() {
+        let credentials = credentials();
+        let bridge = Bridge::new(credentials.ip_address, credentials.username.as_str());
+        let mut store = MemoryStore::default();
+        let session = Session::save(bridge.clone(), &credentials, &mut store).unwrap();
+        assert_eq!(session.bridge(), &bridge);
+        assert_eq!(store.credentials, Some(credentials));
+    }
+
+    #[test]
+    fn save_error() {
+        let credentials = credentials();
+        let bridge = Bridge::new(credentials.ip_address, credentials.username.as_str());
+        let mut store = MemoryStore {
+            fail_save: true,
+            ..Default::default()
+        };
+        let result = Session::save(bridge, &credentials, &mut store);
+        assert!(matches!(result, Err(Error::CredentialStore(_))));
+        assert_eq!(store.credentials, None);
+    }
+}
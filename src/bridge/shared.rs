@@ -0,0 +1,39 @@
+use std::hash::{Hash, Hasher};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Mutable state that is shared by all clones of a bridge.
+///
+/// The state is ignored when comparing or hashing, so that clones of a bridge stay equal while
+/// the state changes.
+#[derive(Debug, Default)]
+pub(crate) struct Shared<T> {
+    value: Mutex<T>,
+}
+
+impl<T> Shared<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self {
+            value: Mutex::new(value),
+        }
+    }
+
+    /// Locks the state.
+    ///
+    /// A panic while the state was locked does not poison it, because every modification of the
+    /// state leaves it valid.
+    pub(crate) fn lock(&self) -> MutexGuard<'_, T> {
+        self.value.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> PartialEq for Shared<T> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for Shared<T> {}
+
+impl<T> Hash for Shared<T> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
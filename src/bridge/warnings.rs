@@ -0,0 +1,40 @@
+use super::shared::Shared;
+
+/// Non-fatal problem that occurred while parsing a response of the bridge.
+///
+/// Returned by [`Bridge::last_warnings`]. Missing attributes are not reported (see
+/// [`Bridge::with_warnings`]).
+///
+/// [`Bridge::last_warnings`]: crate::Bridge::last_warnings
+/// [`Bridge::with_warnings`]: crate::Bridge::with_warnings
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Warning {
+    /// The response contained an attribute that is not known by this library, for example because
+    /// it was added in a newer firmware of the bridge. Contains the path of the attribute, like
+    /// `1.state.newattribute`.
+    UnknownAttribute(String),
+}
+
+/// Warnings of the last parsed response.
+#[derive(Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct WarningLog {
+    warnings: Shared<Vec<Warning>>,
+}
+
+impl WarningLog {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the warnings with the warnings of a newly parsed response.
+    pub(crate) fn replace(&self, warnings: Vec<Warning>) {
+        for warning in &warnings {
+            log::debug!("Warning while parsing response: {:?}", warning);
+        }
+        *self.warnings.lock() = warnings;
+    }
+
+    pub(crate) fn get(&self) -> Vec<Warning> {
+        self.warnings.lock().clone()
+    }
+}
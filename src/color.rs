@@ -61,8 +61,8 @@ impl Color {
         let z = red * 0.000_000 + green * 0.053_077 + blue * 1.035_763;
         Self {
             space_coordinates: (
-                x / (x + y + z + std::f32::MIN_POSITIVE),
-                y / (x + y + z + std::f32::MIN_POSITIVE),
+                x / (x + y + z + f32::MIN_POSITIVE),
+                y / (x + y + z + f32::MIN_POSITIVE),
             ),
             brightness: Some((y * 255.0) as u8),
         }
@@ -117,6 +117,196 @@ impl Color {
     }
 }
 
+/// A white tone that can be set by its name.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NamedColor {
+    /// Name of the color.
+    pub name: &'static str,
+    /// Color temperature in mired.
+    pub color_temperature: u16,
+    /// Color space coordinates that match the color temperature.
+    pub space_coordinates: (f32, f32),
+}
+
+/// White tones that can be set by their name.
+///
+/// These are the white tones that are commonly used in the Philips Hue app, from warm to cold:
+///
+/// | Name        | Color temperature | Color space coordinates |
+/// |-------------|-------------------|-------------------------|
+/// | candle      | 500               | (0.5267, 0.4133)        |
+/// | warm white  | 454               | (0.5016, 0.4153)        |
+/// | relax       | 447               | (0.5019, 0.4152)        |
+/// | reading     | 346               | (0.4449, 0.4066)        |
+/// | concentrate | 233               | (0.3690, 0.3719)        |
+/// | energize    | 156               | (0.3143, 0.3301)        |
+/// | daylight    | 153               | (0.3131, 0.3290)        |
+pub const NAMED_COLORS: &[NamedColor] = &[
+    NamedColor {
+        name: "candle",
+        color_temperature: 500,
+        space_coordinates: (0.5267, 0.4133),
+    },
+    NamedColor {
+        name: "warm white",
+        color_temperature: 454,
+        space_coordinates: (0.5016, 0.4153),
+    },
+    NamedColor {
+        name: "relax",
+        color_temperature: 447,
+        space_coordinates: (0.5019, 0.4152),
+    },
+    NamedColor {
+        name: "reading",
+        color_temperature: 346,
+        space_coordinates: (0.4449, 0.4066),
+    },
+    NamedColor {
+        name: "concentrate",
+        color_temperature: 233,
+        space_coordinates: (0.3690, 0.3719),
+    },
+    NamedColor {
+        name: "energize",
+        color_temperature: 156,
+        space_coordinates: (0.3143, 0.3301),
+    },
+    NamedColor {
+        name: "daylight",
+        color_temperature: 153,
+        space_coordinates: (0.3131, 0.3290),
+    },
+];
+
+/// Returns the white tone with the given name.
+///
+/// The name is compared case-insensitively to the names in [`NAMED_COLORS`].
+pub fn named(name: &str) -> Option<&'static NamedColor> {
+    NAMED_COLORS
+        .iter()
+        .find(|v| v.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Converts color space coordinates and a brightness to approximate rgb values.
+///
+/// This is the inverse of [`Color::from_rgb`]. The brightness is between 0 and 254, like the
+/// brightness of a light. The result only approximates the color that is emitted by a light and is
+/// meant for displaying it.
+///
+/// # Examples
+///
+/// ```
+/// let (red, green, blue) = huelib::color::space_coordinates_to_rgb(0.675, 0.322, 254);
+/// assert_eq!((red, green, blue), (255, 93, 0));
+/// ```
+pub fn space_coordinates_to_rgb(x: f32, y: f32, brightness: u8) -> (u8, u8, u8) {
+    if y <= 0.0 {
+        return (0, 0, 0);
+    }
+    let z = 1.0 - x - y;
+    let (x, z) = (x / y, z / y);
+    let red = x * 1.611_757 - 0.202_805 - z * 0.302_298;
+    let green = -x * 0.509_057 + 1.411_914 + z * 0.066_07;
+    let blue = x * 0.026_086 - 0.072_353 + z * 0.962_086;
+    let max = red.max(green).max(blue).max(f32::MIN_POSITIVE);
+    let gamma_correct = |v: f32| {
+        let v = (v / max).max(0.0);
+        if v <= 0.003_130_8 {
+            v * 12.92
+        } else {
+            (1.0 + 0.055) * v.powf(1.0 / 2.4) - 0.055
+        }
+    };
+    scale_rgb(
+        (
+            gamma_correct(red),
+            gamma_correct(green),
+            gamma_correct(blue),
+        ),
+        brightness,
+    )
+}
+
+/// Rotates the hue of the color with the given color space coordinates.
+///
+/// The coordinates are converted to rgb values (see [`space_coordinates_to_rgb`]), the hue of
+/// these values is rotated by the given angle in degrees and the result is converted back with
+/// [`Color::from_rgb`]. Rotating by 180 degrees returns the complementary color. Colors without a
+/// hue, like white, are not changed. The result may be outside of the gamut of a light.
+///
+/// # Examples
+///
+/// ```
+/// let (x, y) = huelib::color::rotate_hue(0.675, 0.322, 120.0);
+/// assert!(y > 0.6);
+/// ```
+pub fn rotate_hue(x: f32, y: f32, degrees: f32) -> (f32, f32) {
+    let (red, green, blue) = space_coordinates_to_rgb(x, y, 254);
+    let (red, green, blue) = (f32::from(red), f32::from(green), f32::from(blue));
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let delta = max - min;
+    if delta <= 0.0 {
+        return (x, y);
+    }
+    let hue = if max == red {
+        ((green - blue) / delta).rem_euclid(6.0)
+    } else if max == green {
+        (blue - red) / delta + 2.0
+    } else {
+        (red - green) / delta + 4.0
+    };
+    let hue = (hue + degrees / 60.0).rem_euclid(6.0);
+    let component = |n: f32| {
+        let k = (n + hue) % 6.0;
+        let v = max - delta * k.min(4.0 - k).clamp(0.0, 1.0);
+        v.round() as u8
+    };
+    Color::from_rgb(component(5.0), component(3.0), component(1.0)).space_coordinates
+}
+
+/// Converts a color temperature in mired and a brightness to approximate rgb values.
+///
+/// The brightness is between 0 and 254, like the brightness of a light. The result only
+/// approximates the color that is emitted by a light and is meant for displaying it.
+///
+/// # Examples
+///
+/// ```
+/// let (red, green, blue) = huelib::color::color_temperature_to_rgb(153, 254);
+/// assert_eq!((red, green, blue), (255, 255, 251));
+/// ```
+pub fn color_temperature_to_rgb(color_temperature: u16, brightness: u8) -> (u8, u8, u8) {
+    // NOTE: More information:
+    // https://tannerhelland.com/2012/09/18/convert-temperature-rgb-algorithm-code.html
+    let temperature = 10_000.0 / f32::from(color_temperature.max(1));
+    let red = if temperature <= 66.0 {
+        255.0
+    } else {
+        329.698_73 * (temperature - 60.0).powf(-0.133_204_76)
+    };
+    let green = if temperature <= 66.0 {
+        99.470_8 * temperature.ln() - 161.119_57
+    } else {
+        288.122_17 * (temperature - 60.0).powf(-0.075_514_85)
+    };
+    let blue = if temperature >= 66.0 {
+        255.0
+    } else if temperature <= 19.0 {
+        0.0
+    } else {
+        138.517_73 * (temperature - 10.0).ln() - 305.044_8
+    };
+    scale_rgb((red / 255.0, green / 255.0, blue / 255.0), brightness)
+}
+
+fn scale_rgb((red, green, blue): (f32, f32, f32), brightness: u8) -> (u8, u8, u8) {
+    let scale =
+        |v: f32| (v.clamp(0.0, 1.0) * 255.0 * f32::from(brightness.min(254)) / 254.0).round() as u8;
+    (scale(red), scale(green), scale(blue))
+}
+
 /// Errors that can occur while parsing a hex string to a color.
 #[derive(Clone, Debug, Eq, PartialEq, ThisError)]
 pub enum ParseHexError {
@@ -132,6 +322,52 @@ pub enum ParseHexError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn named_color() {
+        let color = named("Warm White").unwrap();
+        assert_eq!(color.color_temperature, 454);
+        assert_eq!(
+            named("energize").unwrap().space_coordinates,
+            (0.3143, 0.3301)
+        );
+        assert_eq!(named("unknown"), None);
+    }
+
+    #[test]
+    fn space_coordinates_to_rgb() {
+        for &rgb in &[(255, 0, 0), (0, 255, 0), (0, 0, 255), (255, 255, 255)] {
+            let color = Color::from_rgb(rgb.0, rgb.1, rgb.2);
+            let (x, y) = color.space_coordinates;
+            assert_eq!(super::space_coordinates_to_rgb(x, y, 254), rgb);
+        }
+        assert_eq!(super::space_coordinates_to_rgb(0.675, 0.322, 0), (0, 0, 0));
+        assert_eq!(super::space_coordinates_to_rgb(0.0, 0.0, 254), (0, 0, 0));
+    }
+
+    #[test]
+    fn rotate_hue() {
+        let rotate = |rgb: (u8, u8, u8), degrees| {
+            let (x, y) = Color::from_rgb(rgb.0, rgb.1, rgb.2).space_coordinates;
+            let (x, y) = super::rotate_hue(x, y, degrees);
+            super::space_coordinates_to_rgb(x, y, 254)
+        };
+        assert_eq!(rotate((255, 0, 0), 120.0), (0, 255, 0));
+        assert_eq!(rotate((255, 0, 0), -120.0), (0, 0, 255));
+        assert_eq!(rotate((255, 0, 0), 180.0), (0, 255, 255));
+        assert_eq!(rotate((0, 255, 0), 360.0), (0, 255, 0));
+        assert_eq!(super::rotate_hue(0.3127, 0.329, 90.0), (0.3127, 0.329));
+    }
+
+    #[test]
+    fn color_temperature_to_rgb() {
+        let (red, green, blue) = super::color_temperature_to_rgb(500, 254);
+        assert_eq!(red, 255);
+        assert!(green < red && blue < green);
+        let (red, _, blue) = super::color_temperature_to_rgb(153, 254);
+        assert!(red >= 250 && blue >= 250);
+        assert_eq!(super::color_temperature_to_rgb(153, 0), (0, 0, 0));
+    }
+
     #[test]
     fn space_coordinates() {
         let color = Color::from_space_coordinates(0.1, 0.2);
@@ -0,0 +1,257 @@
+use crate::{bridge, Bridge, Error, Result};
+use openssl::ssl::{
+    self, HandshakeError, Ssl, SslContext, SslMethod, SslStream, SslVerifyMode, SslVersion,
+};
+use std::io::{self, Read, Write};
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::time::Duration;
+
+/// Port of the entertainment streaming API.
+pub const PORT: u16 = 2100;
+
+/// Maximum number of lights that can be sent in one message.
+pub const MAX_LIGHTS: usize = 10;
+
+/// Cipher suite that is used by the bridge.
+const CIPHER_LIST: &str = "PSK-AES128-GCM-SHA256";
+
+/// Timeout of the DTLS handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Color of a light in a message of an entertainment stream.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LightColor {
+    /// Identifier of the light.
+    pub light_id: u16,
+    /// X coordinate of the color in the CIE color space, between 0 and 1.
+    pub x: f32,
+    /// Y coordinate of the color in the CIE color space, between 0 and 1.
+    pub y: f32,
+    /// Brightness of the light, between 0 and 1.
+    pub brightness: f32,
+}
+
+impl LightColor {
+    /// Creates a new [`LightColor`].
+    pub fn new(light_id: u16, x: f32, y: f32, brightness: f32) -> Self {
+        Self {
+            light_id,
+            x,
+            y,
+            brightness,
+        }
+    }
+}
+
+/// Streaming session of an entertainment group.
+///
+/// The session is started with [`Stream::start`], which activates streaming for the group (see
+/// [`Bridge::set_group_stream_active`]) and
+/// establishes a DTLS connection to the bridge. The username of the bridge and the clientkey that
+/// was generated with [`register_user_with_clientkey`] are used as pre-shared key.
+///
+/// [`register_user_with_clientkey`]: crate::bridge::register_user_with_clientkey
+///
+/// The session should be ended with [`Stream::stop`], which returns errors that occur while
+/// deactivating streaming. If the stream is dropped without being stopped, for example while a
+/// panic unwinds, the connection is closed and streaming is deactivated on a best-effort basis
+/// and errors are ignored. Nothing is sent if the program is terminated abruptly (e.g. by
+/// [`std::process::exit`] or an aborting panic), in that case the bridge releases the group when
+/// the stream times out.
+///
+/// # Examples
+///
+/// Set the light with the identifier `1` in the entertainment group `2` to red:
+/// ```no_run
+/// # fn main() -> huelib::Result<()> {
+/// # use huelib::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// use huelib::entertainment::{LightColor, Stream};
+///
+/// // let bridge = Bridge::new(...);
+/// let mut stream = Stream::start(&bridge, "2", "0123456789ABCDEF0123456789ABCDEF")?;
+/// stream.send(&[LightColor::new(1, 0.675, 0.322, 1.0)])?;
+/// stream.stop()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Stream<'a> {
+    bridge: &'a Bridge,
+    group_id: String,
+    ssl: SslStream<Socket>,
+    sequence: u8,
+    stopped: bool,
+}
+
+impl<'a> Stream<'a> {
+    /// Activates streaming for the group and connects to the bridge.
+    ///
+    /// The clientkey must be the hexadecimal encoded key that was returned when registering the
+    /// user.
+    pub fn start<S>(bridge: &'a Bridge, group_id: S, clientkey: &str) -> Result<Self>
+    where
+        S: Into<String>,
+    {
+        let psk = bridge::decode_clientkey(clientkey)?.to_vec();
+        let group_id = group_id.into();
+        set_stream_active(bridge, &group_id, true)?;
+        let ssl = match connect(bridge, psk) {
+            Ok(v) => v,
+            Err(e) => {
+                let _ = set_stream_active(bridge, &group_id, false);
+                return Err(e);
+            }
+        };
+        Ok(Self {
+            bridge,
+            group_id,
+            ssl,
+            sequence: 0,
+            stopped: false,
+        })
+    }
+
+    /// Returns the identifier of the streaming group.
+    pub fn group_id(&self) -> &str {
+        &self.group_id
+    }
+
+    /// Sends the colors of lights to the bridge.
+    ///
+    /// Lights that are not included keep their last color.
+    pub fn send(&mut self, lights: &[LightColor]) -> Result<()> {
+        if lights.len() > MAX_LIGHTS {
+            return Err(Error::ValueOutOfRange {
+                attribute: "lights",
+                value: lights.len() as i64,
+                min: 0,
+                max: MAX_LIGHTS as i64,
+            });
+        }
+        let message = encode_message(self.sequence, lights);
+        self.sequence = self.sequence.wrapping_add(1);
+        self.ssl.ssl_write(&message)?;
+        Ok(())
+    }
+
+    /// Closes the connection and deactivates streaming for the group.
+    ///
+    /// Streaming is deactivated even if closing the connection fails.
+    pub fn stop(mut self) -> Result<()> {
+        self.stopped = true;
+        let shutdown = self.ssl.shutdown();
+        set_stream_active(self.bridge, &self.group_id, false)?;
+        shutdown?;
+        Ok(())
+    }
+}
+
+impl Drop for Stream<'_> {
+    fn drop(&mut self) {
+        if !self.stopped {
+            let _ = self.ssl.shutdown();
+            let _ = set_stream_active(self.bridge, &self.group_id, false);
+        }
+    }
+}
+
+/// UDP socket that is connected to the bridge.
+#[derive(Debug)]
+struct Socket(UdpSocket);
+
+impl Read for Socket {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.recv(buf)
+    }
+}
+
+impl Write for Socket {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.send(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn set_stream_active(bridge: &Bridge, group_id: &str, active: bool) -> Result<()> {
+    for i in bridge.set_group_stream_active(group_id, active)? {
+        i.into_result()?;
+    }
+    Ok(())
+}
+
+fn connect(bridge: &Bridge, psk: Vec<u8>) -> Result<SslStream<Socket>> {
+    let identity = bridge.username().as_bytes().to_vec();
+    let mut context = SslContext::builder(SslMethod::dtls())?;
+    context.set_min_proto_version(Some(SslVersion::DTLS1_2))?;
+    context.set_cipher_list(CIPHER_LIST)?;
+    context.set_verify(SslVerifyMode::NONE);
+    context.set_psk_client_callback(move |_, _, identity_buf, psk_buf| {
+        if identity.len() >= identity_buf.len() || psk.len() > psk_buf.len() {
+            return Err(openssl::error::ErrorStack::get());
+        }
+        identity_buf[..identity.len()].copy_from_slice(&identity);
+        identity_buf[identity.len()] = 0;
+        psk_buf[..psk.len()].copy_from_slice(&psk);
+        Ok(psk.len())
+    });
+    let context = context.build();
+
+    let local_address = match bridge.ip_address() {
+        IpAddr::V4(_) => "0.0.0.0:0",
+        IpAddr::V6(_) => "[::]:0",
+    };
+    let socket = UdpSocket::bind(local_address).map_err(Error::StreamSocket)?;
+    socket
+        .connect(SocketAddr::new(*bridge.ip_address(), PORT))
+        .map_err(Error::StreamSocket)?;
+    socket
+        .set_read_timeout(Some(HANDSHAKE_TIMEOUT))
+        .map_err(Error::StreamSocket)?;
+
+    let ssl = Ssl::new(&context)?;
+    let stream = ssl.connect(Socket(socket)).map_err(|e| match e {
+        HandshakeError::SetupFailure(e) => ssl::Error::from(e),
+        HandshakeError::Failure(e) | HandshakeError::WouldBlock(e) => e.into_error(),
+    })?;
+    stream
+        .get_ref()
+        .0
+        .set_read_timeout(None)
+        .map_err(Error::StreamSocket)?;
+    Ok(stream)
+}
+
+fn encode_message(sequence: u8, lights: &[LightColor]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(16 + lights.len() * 9);
+    message.extend_from_slice(b"HueStream");
+    // Version 1.0, sequence number, reserved, XY color space, reserved
+    message.extend_from_slice(&[0x01, 0x00, sequence, 0x00, 0x00, 0x01, 0x00]);
+    for light in lights {
+        message.push(0x00);
+        message.extend_from_slice(&light.light_id.to_be_bytes());
+        for value in &[light.x, light.y, light.brightness] {
+            let value = (value.clamp(0.0, 1.0) * f32::from(u16::MAX)).round() as u16;
+            message.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_message() {
+        let message = super::encode_message(7, &[LightColor::new(3, 0.0, 1.0, 2.0)]);
+        let mut expected = b"HueStream".to_vec();
+        expected.extend_from_slice(&[0x01, 0x00, 0x07, 0x00, 0x00, 0x01, 0x00]);
+        expected.extend_from_slice(&[0x00, 0x00, 0x03, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff]);
+        assert_eq!(message, expected);
+    }
+}
@@ -1,12 +1,15 @@
-use crate::response::Error as ResponseError;
+use crate::bridge::ImportedIds;
+use crate::response::{Error as ResponseError, ErrorKind as ResponseErrorKind};
 use chrono::ParseError as ChronoParseError;
+#[cfg(feature = "entertainment")]
+use openssl::{error::ErrorStack as OpenSslErrorStack, ssl::Error as OpenSslError};
 use serde_json::Error as SerdeJsonError;
 #[cfg(feature = "upnp-description")]
 use serde_xml_rs::Error as SerdeXmlError;
-use std::result::Result as StdResult;
-use std::{io::Error as IoError, net::AddrParseError};
+use std::io::{Error as IoError, ErrorKind as IoErrorKind};
+use std::{error::Error as StdError, net::AddrParseError, result::Result as StdResult};
 use thiserror::Error as ThisError;
-use ureq::Error as UreqError;
+use ureq::{Error as UreqError, ErrorKind as UreqErrorKind};
 
 /// Alias for `Result<T, huelib::Error>`.
 pub type Result<T> = StdResult<T, Error>;
@@ -18,10 +21,81 @@ pub enum Error {
     #[error("Failed to get username")]
     GetUsername,
 
+    /// Error that can occur when a username is empty or contains characters that are not allowed.
+    #[error("Invalid username")]
+    InvalidUsername,
+
+    /// Error that can occur when a clientkey is not a 16 byte hexadecimal encoded key.
+    #[error("Invalid clientkey")]
+    InvalidClientKey,
+
+    /// Error that can occur when no resource with a given name exists.
+    #[error("Found no resource with name `{0}`")]
+    NameNotFound(String),
+
     /// Error that can occur when the identifier of a newly created resource cannot be obtained.
     #[error("Failed to get identifier of created resource")]
     GetCreatedId,
 
+    /// Error that can occur when a value is outside of the range that is supported by a resource.
+    #[error("Value {value} of attribute `{attribute}` is not in range {min}..={max}")]
+    ValueOutOfRange {
+        /// Name of the attribute.
+        attribute: &'static str,
+        /// The value that is out of range.
+        value: i64,
+        /// Minimal supported value.
+        min: i64,
+        /// Maximal supported value.
+        max: i64,
+    },
+
+    /// Error that can occur when an attribute that is required by a resource is not set.
+    #[error("Attribute `{0}` is required")]
+    MissingAttribute(&'static str),
+
+    /// Error that can occur when a coordinate of the location of a light is not between -1 and 1.
+    #[error("Location of light `{0}` is not in range -1..=1")]
+    InvalidLocation(String),
+
+    /// Error that can occur when a timezone is not supported by the bridge.
+    #[error("Timezone `{0}` is not supported by the bridge")]
+    InvalidTimezone(String),
+
+    /// Error that can occur when a Zigbee channel is not one of the channels used by bridges.
+    #[error("Zigbee channel {0} is not one of 11, 15, 20 or 25")]
+    InvalidZigbeeChannel(u8),
+
+    /// Error that can occur when addresses do not reference existing resources, for example the
+    /// addresses of a rule or of the lights of a scene.
+    #[error("Addresses do not reference existing resources: {}", .0.join(", "))]
+    UnresolvedAddresses(Vec<String>),
+
+    /// Error that can occur when importing resources fails.
+    ///
+    /// Contains the identifiers of the resources that were created before the import failed, so
+    /// they can be found or deleted.
+    #[error("Failed to import resources")]
+    Import {
+        /// Identifiers of the resources that were already created.
+        imported: Box<ImportedIds>,
+        /// The error that stopped the import.
+        #[source]
+        source: Box<Error>,
+    },
+
+    /// Error that can occur when a light does not reach a state before a timeout.
+    #[error("Light `{0}` did not reach the requested state in time")]
+    StateNotReached(String),
+
+    /// Error that can occur when no bridge is discovered in the local network.
+    #[error("Found no bridge in the local network")]
+    BridgeNotFound,
+
+    /// Error that can occur when credentials cannot be loaded from or saved to a store.
+    #[error("Failed to access the credential store")]
+    CredentialStore(#[source] IoError),
+
     /// Error that can occur while converting a string to a date.
     #[error("Failed to parse date")]
     ParseDate(#[from] ChronoParseError),
@@ -42,6 +116,18 @@ pub enum Error {
     #[error("Failed to send HTTP request")]
     Request(#[from] Box<UreqError>),
 
+    /// Error that can occur when a connection to the server cannot be established.
+    #[error("Failed to connect to the server")]
+    Connection(#[source] Box<UreqError>),
+
+    /// Error that can occur when a request timed out.
+    #[error("Request timed out")]
+    Timeout(#[source] Box<UreqError>),
+
+    /// Error that can occur when the server responds with an error HTTP status code.
+    #[error("Server responded with HTTP status code {0}")]
+    HttpStatus(u16),
+
     #[cfg(feature = "upnp-description")]
     /// Error that can occur when deserializing [`Description`].
     ///
@@ -49,13 +135,98 @@ pub enum Error {
     #[error("Failed to parse description")]
     ParseDescription(#[from] SerdeXmlError),
 
+    #[cfg(feature = "entertainment")]
+    /// Error that can occur while using the socket of an entertainment stream.
+    #[error("Failed to use entertainment stream socket")]
+    StreamSocket(#[source] IoError),
+
+    #[cfg(feature = "entertainment")]
+    /// Error that can occur in the DTLS connection of an entertainment stream.
+    #[error("Failed to communicate over DTLS")]
+    Dtls(#[from] OpenSslError),
+
     /// Error that is returned by the Philips Hue API.
     #[error("Error returned from Philips Hue API")]
     Response(#[from] ResponseError),
 }
 
+impl Error {
+    /// Returns whether the error was returned because the user is not authorized.
+    ///
+    /// This is the case if the username is not on the whitelist of the bridge.
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, Self::Response(e) if e.kind == ResponseErrorKind::UnauthorizedUser)
+    }
+
+    /// Returns whether the error was returned because the link button of the bridge was not
+    /// pressed while registering a user.
+    pub fn is_link_button_not_pressed(&self) -> bool {
+        matches!(self, Self::Response(e) if e.kind == ResponseErrorKind::LinkButtonNotPressed)
+    }
+}
+
 impl From<UreqError> for Error {
     fn from(ureq_error: UreqError) -> Self {
-        Self::Request(Box::new(ureq_error))
+        if let UreqError::Status(code, _) = ureq_error {
+            return Self::HttpStatus(code);
+        }
+        let timed_out = ureq_error
+            .source()
+            .and_then(|e| e.downcast_ref::<IoError>())
+            .is_some_and(|e| matches!(e.kind(), IoErrorKind::TimedOut | IoErrorKind::WouldBlock));
+        if timed_out {
+            return Self::Timeout(Box::new(ureq_error));
+        }
+        match ureq_error.kind() {
+            UreqErrorKind::Dns | UreqErrorKind::ConnectionFailed => {
+                Self::Connection(Box::new(ureq_error))
+            }
+            _ => Self::Request(Box::new(ureq_error)),
+        }
+    }
+}
+
+#[cfg(feature = "entertainment")]
+impl From<OpenSslErrorStack> for Error {
+    fn from(error_stack: OpenSslErrorStack) -> Self {
+        Self::Dtls(error_stack.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_ureq_error() {
+        let response = ureq::Response::new(404, "Not Found", "").unwrap();
+        let error = Error::from(UreqError::Status(404, response));
+        assert!(matches!(error, Error::HttpStatus(404)));
+
+        // A resolver that fails makes the request fail without connecting.
+        let agent = ureq::AgentBuilder::new()
+            .resolver(|_: &str| -> std::io::Result<Vec<std::net::SocketAddr>> {
+                Err(IoError::new(IoErrorKind::NotFound, "test"))
+            })
+            .build();
+        let error = Error::from(agent.get("http://bridge.invalid").call().unwrap_err());
+        assert!(matches!(error, Error::Connection(_)));
+    }
+
+    #[test]
+    fn is_unauthorized() {
+        let response_error = |kind| ResponseError {
+            kind,
+            address: "/".to_owned(),
+            description: "test".to_owned(),
+        };
+        let error = Error::from(response_error(ResponseErrorKind::UnauthorizedUser));
+        assert!(error.is_unauthorized());
+        let error = Error::from(response_error(ResponseErrorKind::ResourceNotAvailable));
+        assert!(!error.is_unauthorized());
+        assert!(!Error::HttpStatus(403).is_unauthorized());
+        let error = Error::from(response_error(ResponseErrorKind::LinkButtonNotPressed));
+        assert!(error.is_link_button_not_pressed());
+        assert!(!error.is_unauthorized());
     }
 }
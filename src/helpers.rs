@@ -0,0 +1,175 @@
+use crate::resource::{group, rule, schedule, sensor};
+use crate::{Bridge, Result};
+
+/// Identifiers of the resources that were created by [`create_timed_flag_automation`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TimedFlagAutomation {
+    /// Identifier of the `CLIPGenericFlag` sensor.
+    pub sensor_id: String,
+    /// Identifier of the rule that reacts to the flag.
+    pub rule_id: String,
+    /// Identifier of the schedule that sets the flag.
+    pub schedule_id: String,
+}
+
+/// Creates a schedule that sets a flag at the given time and a rule that modifies the state of a
+/// group when the flag is set.
+///
+/// This creates a `CLIPGenericFlag` sensor, a rule that applies `action` to the group and resets
+/// the flag, and a schedule that sets the flag at `local_time` (see [`schedule::LocalTime`] for
+/// the format). All resources are named `name`.
+///
+/// If creating one of the resources fails, the resources that were already created are deleted
+/// again before the error is returned.
+///
+/// # Examples
+///
+/// Turn on the lights of the group `1` every day at 7:00:
+/// ```no_run
+/// # fn main() -> huelib::Result<()> {
+/// # use huelib::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// use huelib::{helpers, resource::group};
+///
+/// // let bridge = Bridge::new(...);
+/// let action = group::StateModifier::new().with_on(true);
+/// let automation =
+///     helpers::create_timed_flag_automation(&bridge, "Wake up", "W127/T07:00:00", "1", &action)?;
+/// println!("Created rule {}", automation.rule_id);
+/// # Ok(())
+/// # }
+/// ```
+pub fn create_timed_flag_automation<S>(
+    bridge: &Bridge,
+    name: &str,
+    local_time: &str,
+    group_id: S,
+    action: &group::StateModifier,
+) -> Result<TimedFlagAutomation>
+where
+    S: Into<String>,
+{
+    let sensor_id = bridge.create_sensor(&flag_sensor(name))?;
+
+    let rule_id = match flag_rule(name, &sensor_id, group_id.into(), action)
+        .and_then(|creator| bridge.create_rule(&creator))
+    {
+        Ok(v) => v,
+        Err(e) => {
+            let _ = bridge.delete_sensor(sensor_id.as_str());
+            return Err(e);
+        }
+    };
+
+    let schedule_id = match flag_schedule(name, local_time, &sensor_id, bridge.username())
+        .and_then(|creator| bridge.create_schedule(&creator))
+    {
+        Ok(v) => v,
+        Err(e) => {
+            let _ = bridge.delete_rule(rule_id.as_str());
+            let _ = bridge.delete_sensor(sensor_id.as_str());
+            return Err(e);
+        }
+    };
+
+    Ok(TimedFlagAutomation {
+        sensor_id,
+        rule_id,
+        schedule_id,
+    })
+}
+
+fn flag_sensor(name: &str) -> sensor::Creator {
+    sensor::Creator::new(
+        name.to_owned(),
+        "CLIPGenericFlag".to_owned(),
+        "huelib flag".to_owned(),
+        "1.0".to_owned(),
+        format!("huelib-flag-{}", name),
+        "huelib".to_owned(),
+    )
+}
+
+fn flag_rule(
+    name: &str,
+    sensor_id: &str,
+    group_id: String,
+    action: &group::StateModifier,
+) -> Result<rule::Creator> {
+    let conditions = vec![
+        rule::Condition {
+            address: format!("/sensors/{}/state/flag", sensor_id),
+            operator: rule::ConditionOperator::Equals,
+            value: Some("true".to_owned()),
+        },
+        rule::Condition {
+            address: format!("/sensors/{}/state/lastupdated", sensor_id),
+            operator: rule::ConditionOperator::Dx,
+            value: None,
+        },
+    ];
+    let actions = vec![
+        rule::Action::from_modifier(action, group_id)?,
+        rule::Action::from_modifier(
+            &sensor::StateModifier::new().with_flag(false),
+            sensor_id.to_owned(),
+        )?,
+    ];
+    Ok(rule::Creator::new(conditions, actions).with_name(name.to_owned()))
+}
+
+fn flag_schedule(
+    name: &str,
+    local_time: &str,
+    sensor_id: &str,
+    username: &str,
+) -> Result<schedule::Creator> {
+    let command = schedule::Command::from_modifier(
+        &sensor::StateModifier::new().with_flag(true),
+        sensor_id.to_owned(),
+        username,
+    )?;
+    Ok(schedule::Creator::new(command, local_time).with_name(name.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn flag_rule() {
+        let action = group::StateModifier::new().with_on(true);
+        let creator = super::flag_rule("test", "5", "1".into(), &action).unwrap();
+        let creator_json = serde_json::to_value(creator).unwrap();
+        let expected_json = json!({
+            "name": "test",
+            "conditions": [
+                {"address": "/sensors/5/state/flag", "operator": "eq", "value": "true"},
+                {"address": "/sensors/5/state/lastupdated", "operator": "dx"}
+            ],
+            "actions": [
+                {"address": "/groups/1/action", "method": "PUT", "body": {"on": true}},
+                {"address": "/sensors/5/state", "method": "PUT", "body": {"flag": false}}
+            ]
+        });
+        assert_eq!(creator_json, expected_json);
+    }
+
+    #[test]
+    fn flag_schedule() {
+        let creator = super::flag_schedule("test", "W127/T07:00:00", "5", "user").unwrap();
+        let creator_json = serde_json::to_value(creator).unwrap();
+        let expected_json = json!({
+            "name": "test",
+            "command": {
+                "address": "/api/user/sensors/5/state",
+                "method": "PUT",
+                "body": {"flag": true}
+            },
+            "localtime": "W127/T07:00:00"
+        });
+        assert_eq!(creator_json, expected_json);
+    }
+}
@@ -14,13 +14,33 @@
 //! [serde_json]: https://github.com/serde-rs/json
 //! [serde_repr]: https://github.com/dtolnay/serde-repr
 //!
+//! Requests are logged using the [log] crate. The method, path and response status of every
+//! request are logged at the debug level and the bodies of requests and responses at the trace
+//! level. The username is not included in the logged paths. Nothing is logged unless a logger is
+//! set up by the application.
+//!
+//! [log]: https://github.com/rust-lang/log
+//!
 //! # Features
 //!
 //! - `upnp-description`: Adds support for accessing the UPnP description of a bridge. See the
-//! [`bridge::Description`] struct for more information.
+//!   [`bridge::Description`] struct for more information.
 //! - `old-api`: Minimal effort support for older api versions. Useful for users of the no longer
-//! supported Hue v1 bridge. This lowers the supported API version to `1.16` not all features
-//! are guarenteed to work.
+//!   supported Hue v1 bridge. This lowers the supported API version to `1.16` not all features
+//!   are guarenteed to work.
+//! - `lenient`: More lenient deserialization for emulated bridges (e.g. diyHue). Numbers and
+//!   booleans are also accepted when they are encoded as strings and missing attributes are set to
+//!   their default value. This affects the state of lights, groups and sensors, the configuration
+//!   of sensors and the model identifier, unique identifier and software version of lights.
+//! - `entertainment`: Adds support for streaming colors to entertainment groups over DTLS. See the
+//!   [`entertainment::Stream`] struct for more information. This requires OpenSSL.
+//! - `tracing`: Adds spans of the [tracing] crate to the methods of [`Bridge`] that send requests.
+//!   Every method has a span with its name and, for methods of a single resource, an `id` field
+//!   with the identifier of the resource. It contains a span for each HTTP request with the
+//!   method, path and response status. Errors that are returned by the methods are recorded as
+//!   events.
+//!
+//! [tracing]: https://github.com/tokio-rs/tracing
 //!
 //! # Connecting to a bridge
 //!
@@ -217,12 +237,20 @@ mod error;
 pub mod bridge;
 /// Module for generating colors.
 pub mod color;
+#[cfg(feature = "entertainment")]
+/// Module for streaming colors to entertainment groups.
+pub mod entertainment;
+/// Helpers that combine multiple requests.
+pub mod helpers;
 /// Module for bridge resources.
 pub mod resource;
 /// Responses returned from the Philips Hue API.
 pub mod response;
+/// Module for managing rooms.
+pub mod room;
 
 pub use bridge::Bridge;
 pub use color::Color;
 pub use error::{Error, Result};
 pub use response::Response;
+pub use room::Room;
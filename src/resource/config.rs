@@ -1,4 +1,5 @@
-use crate::{resource, util};
+use crate::resource::{self, Capabilities};
+use crate::{util, Error};
 use chrono::{NaiveDateTime, NaiveTime};
 use derive_setters::Setters;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -11,8 +12,10 @@ pub struct Config {
     /// Name of the bridge.
     pub name: String,
     /// Information about software updates.
+    ///
+    /// Not available on older bridges.
     #[serde(rename = "swupdate2")]
-    pub software_update: SoftwareUpdate,
+    pub software_update: Option<SoftwareUpdate>,
     /// Software version of the bridge.
     #[serde(rename = "swversion")]
     pub software_version: String,
@@ -34,34 +37,51 @@ pub struct Config {
     pub gateway: IpAddr,
     /// Whether the IP address of the bridge is obtained with DHCP.
     pub dhcp: bool,
+    /// Address of the proxy that is used by the bridge, or `None` if no proxy is used.
+    #[serde(
+        rename = "proxyaddress",
+        default,
+        deserialize_with = "util::deserialize_option_ip_addr"
+    )]
+    pub proxy_address: Option<IpAddr>,
+    /// Port of the proxy that is used by the bridge, 0 if no proxy is used.
+    #[serde(rename = "proxyport", default)]
+    pub proxy_port: u16,
     /// Whether the bridge is registered to synchronize data with a portal account.
     #[serde(rename = "portalservices")]
     pub portal_services: bool,
     /// Status of the portal connection.
+    ///
+    /// Not available on older bridges.
     #[serde(rename = "portalconnection")]
-    pub portal_connection: ServiceStatus,
+    pub portal_connection: Option<ServiceStatus>,
     /// Portal state of the bridge.
+    ///
+    /// Not available on older bridges.
     #[serde(rename = "portalstate")]
-    pub portal_state: PortalState,
+    pub portal_state: Option<PortalState>,
     /// Internet services of the bridge.
+    ///
+    /// Not available on older bridges.
     #[serde(rename = "internetservices")]
-    pub internet_services: InternetServices,
+    pub internet_services: Option<InternetServices>,
     /// Current time stored on the bridge.
-    #[serde(rename = "UTC")]
+    #[serde(rename = "UTC", deserialize_with = "util::deserialize_date_time")]
     pub current_time: NaiveDateTime,
     /// Local time of the bridge.
     #[serde(
         rename = "localtime",
+        default,
         deserialize_with = "util::deserialize_option_date_time"
     )]
     pub local_time: Option<NaiveDateTime>,
     /// Timezone of the bridge as OlsenIDs.
-    #[serde(deserialize_with = "util::deserialize_option_string")]
+    #[serde(default, deserialize_with = "util::deserialize_option_string")]
     pub timezone: Option<String>,
     /// The current wireless frequency channel used by the bridge.
     ///
     /// It can take values of 11, 15, 20, 25 or 0 if undefined (factory new).
-    #[serde(rename = "zigbeechannel")]
+    #[serde(rename = "zigbeechannel", default)]
     pub zigbee_channel: u8,
     /// Uniquely identifies the hardware model of the bridge.
     #[serde(rename = "modelid")]
@@ -69,8 +89,8 @@ pub struct Config {
     /// The unique bridge id.
     #[serde(rename = "bridgeid")]
     pub bridge_id: String,
-    #[serde(rename = "factorynew")]
     /// Indicates if bridge settings are factory new.
+    #[serde(rename = "factorynew", default)]
     pub factory_new: bool,
     #[serde(rename = "replacesbridgeid")]
     /// Identifier of the bridge where a backup was restored.
@@ -78,20 +98,119 @@ pub struct Config {
     /// If no backup was restored from another bridge, this will be `None`.
     pub replaces_bridge_id: Option<String>,
     /// The version of the datastore.
-    #[serde(rename = "datastoreversion")]
+    #[serde(rename = "datastoreversion", default)]
     pub datastore_version: String,
     /// Name of the starterkit created in the factory.
-    #[serde(rename = "starterkitid")]
+    #[serde(rename = "starterkitid", default)]
     pub starterkit_id: String,
     /// Backup information about the bridge.
-    pub backup: Backup,
+    ///
+    /// Not available on older bridges.
+    pub backup: Option<Backup>,
     /// Whitelisted users.
-    #[serde(deserialize_with = "deserialize_whitelist")]
+    #[serde(default, deserialize_with = "deserialize_whitelist")]
     pub whitelist: Vec<User>,
 }
 
+impl Config {
+    /// Returns the version of the Philips Hue API as major, minor and patch version.
+    ///
+    /// The version is parsed leniently, missing or malformed parts are `0`. The returned tuple
+    /// can be compared with other versions.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> huelib::Result<()> {
+    /// # use huelib::Bridge;
+    /// # use std::net::{IpAddr, Ipv4Addr};
+    /// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+    /// let config = bridge.get_config()?;
+    /// if config.parsed_api_version() >= (1, 45, 0) {
+    ///     println!("Bridge supports API version 1.45.0");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parsed_api_version(&self) -> (u32, u32, u32) {
+        parse_version(&self.api_version)
+    }
+
+    /// Returns whether the version of the Philips Hue API is at least the given version.
+    pub fn api_version_at_least(&self, version: (u32, u32, u32)) -> bool {
+        self.parsed_api_version() >= version
+    }
+}
+
 impl resource::Resource for Config {}
 
+/// Configuration of a bridge that can be accessed without a registered user.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub struct PublicConfig {
+    /// Name of the bridge.
+    pub name: String,
+    /// Software version of the bridge.
+    #[serde(rename = "swversion")]
+    pub software_version: String,
+    /// The version of the Philips Hue API.
+    #[serde(rename = "apiversion")]
+    pub api_version: String,
+    /// MAC address of the bridge.
+    #[serde(rename = "mac")]
+    pub mac_address: String,
+    /// The unique bridge id.
+    #[serde(rename = "bridgeid")]
+    pub bridge_id: String,
+    /// Indicates if bridge settings are factory new.
+    #[serde(rename = "factorynew")]
+    pub factory_new: bool,
+    /// Identifier of the bridge where a backup was restored.
+    #[serde(rename = "replacesbridgeid")]
+    pub replaces_bridge_id: Option<String>,
+    /// Uniquely identifies the hardware model of the bridge.
+    #[serde(rename = "modelid")]
+    pub model_id: String,
+    /// The version of the datastore.
+    #[serde(rename = "datastoreversion")]
+    pub datastore_version: String,
+    /// Name of the starterkit created in the factory.
+    #[serde(rename = "starterkitid")]
+    pub starterkit_id: String,
+}
+
+impl PublicConfig {
+    /// Returns the version of the Philips Hue API as major, minor and patch version.
+    ///
+    /// The version is parsed leniently, missing or malformed parts are `0`. The returned tuple
+    /// can be compared with other versions.
+    pub fn parsed_api_version(&self) -> (u32, u32, u32) {
+        parse_version(&self.api_version)
+    }
+
+    /// Returns whether the version of the Philips Hue API is at least the given version.
+    pub fn api_version_at_least(&self, version: (u32, u32, u32)) -> bool {
+        self.parsed_api_version() >= version
+    }
+}
+
+impl resource::Resource for PublicConfig {}
+
+/// Parses a version string like `1.45.0` into major, minor and patch version.
+fn parse_version(version: &str) -> (u32, u32, u32) {
+    let mut parts = version.trim().split('.').map(|part| {
+        part.chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>()
+            .parse()
+            .unwrap_or(0)
+    });
+    (
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    )
+}
+
 fn deserialize_whitelist<'de, D: Deserializer<'de>>(
     deserializer: D,
 ) -> Result<Vec<User>, D::Error> {
@@ -111,10 +230,18 @@ pub struct SoftwareUpdate {
     #[serde(rename = "autoinstall")]
     pub auto_install: SoftwareUpdateAutoInstall,
     /// Time of last change in system configuration.
-    #[serde(rename = "lastchange")]
+    #[serde(
+        rename = "lastchange",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
     pub last_change: Option<NaiveDateTime>,
     /// Time of last software update.
-    #[serde(rename = "lastinstall")]
+    #[serde(
+        rename = "lastinstall",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
     pub last_install: Option<NaiveDateTime>,
 }
 
@@ -181,10 +308,17 @@ pub struct InternetServices {
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ServiceStatus {
-    /// The serivce is connected.
+    /// The service is connected.
     Connected,
-    /// The serivce is not connected.
+    /// The service was connected but lost the connection.
     Disconnected,
+    /// The service was never connected, for example because the bridge has no internet access.
+    #[serde(
+        rename = "not-connected",
+        alias = "not connected",
+        alias = "notconnected"
+    )]
+    NotConnected,
 }
 
 /// Backup information about the bridge.
@@ -247,10 +381,16 @@ pub struct User {
     /// Name of the user.
     pub name: String,
     /// Date of the last use of the user.
-    #[serde(rename = "last use date")]
+    #[serde(
+        rename = "last use date",
+        deserialize_with = "util::deserialize_date_time"
+    )]
     pub last_use_date: NaiveDateTime,
     /// Date when the user was created.
-    #[serde(rename = "create date")]
+    #[serde(
+        rename = "create date",
+        deserialize_with = "util::deserialize_date_time"
+    )]
     pub create_date: NaiveDateTime,
 }
 
@@ -261,6 +401,12 @@ impl User {
 }
 
 /// Struct for modifying configuration attributes.
+///
+/// Changing the network settings (IP address, netmask, gateway, DHCP or proxy) can make the
+/// bridge unreachable at its current address. None of the attributes require the link button to
+/// be pressed, but [`linkbutton`] itself can only be written through the portal.
+///
+/// [`linkbutton`]: Self::linkbutton
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Modifier {
@@ -301,7 +447,8 @@ pub struct Modifier {
     pub touchlink: Option<bool>,
     /// Sets the wireless frequency channel used by the bridge.
     ///
-    /// It can take values of 11, 15, 20 or 25.
+    /// It can take values of 11, 15, 20 or 25 (see [`ZIGBEE_CHANNELS`]). Changing the channel
+    /// takes some time, during which the lights and sensors are not reachable.
     #[serde(skip_serializing_if = "Option::is_none", rename = "zigbeechannel")]
     pub zigbee_channel: Option<u8>,
     /// Sets the current time of the bridge in UTC.
@@ -317,6 +464,26 @@ impl Modifier {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Checks whether the values of this modifier are supported by a bridge with the given
+    /// capabilities.
+    ///
+    /// Returns [`Error::InvalidTimezone`] if the timezone is not in the list of available
+    /// timezones and [`Error::InvalidZigbeeChannel`] if the Zigbee channel is not supported.
+    ///
+    /// [`Error::InvalidTimezone`]: crate::Error::InvalidTimezone
+    /// [`Error::InvalidZigbeeChannel`]: crate::Error::InvalidZigbeeChannel
+    pub fn validate(&self, capabilities: &Capabilities) -> crate::Result<()> {
+        if let Some(channel) = self.zigbee_channel {
+            validate_zigbee_channel(channel)?;
+        }
+        if let Some(timezone) = &self.timezone {
+            if !capabilities.timezones.values.contains(timezone) {
+                return Err(Error::InvalidTimezone(timezone.clone()));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl resource::Modifier for Modifier {
@@ -326,6 +493,17 @@ impl resource::Modifier for Modifier {
     }
 }
 
+/// Zigbee channels that can be used by a bridge.
+pub const ZIGBEE_CHANNELS: [u8; 4] = [11, 15, 20, 25];
+
+pub(crate) fn validate_zigbee_channel(channel: u8) -> crate::Result<()> {
+    if ZIGBEE_CHANNELS.contains(&channel) {
+        Ok(())
+    } else {
+        Err(Error::InvalidZigbeeChannel(channel))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -352,8 +530,8 @@ mod tests {
             touchlink: Some(false),
             zigbee_channel: Some(1),
             current_time: Some(NaiveDateTime::new(
-                NaiveDate::from_ymd(2020, 1, 1),
-                NaiveTime::from_hms(0, 0, 0),
+                NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
             )),
             timezone: Some("Europe/Berlin".into()),
         };
@@ -374,4 +552,125 @@ mod tests {
         });
         assert_eq!(modifier_json, expected_json);
     }
+
+    #[test]
+    fn deserialize_config() {
+        let json = json!({
+            "name": "Philips hue",
+            "swversion": "01012917",
+            "apiversion": "1.3.0",
+            "linkbutton": false,
+            "ipaddress": "192.168.1.2",
+            "mac": "00:17:88:00:00:00",
+            "netmask": "255.255.255.0",
+            "gateway": "192.168.1.1",
+            "dhcp": true,
+            "proxyaddress": "none",
+            "proxyport": 0,
+            "portalservices": true,
+            "UTC": "2020-01-01T00:00:00",
+            "modelid": "BSB001",
+            "bridgeid": "001788FFFE000000",
+            "whitelist": {
+                "user": {
+                    "name": "test",
+                    "last use date": "2020-01-01T00:00:00",
+                    "create date": "2019-01-01T00:00:00"
+                }
+            }
+        });
+        let config: Config = serde_json::from_value(json).unwrap();
+        assert_eq!(config.software_update, None);
+        assert_eq!(config.proxy_address, None);
+        assert_eq!(config.proxy_port, 0);
+        assert_eq!(config.internet_services, None);
+        assert_eq!(config.backup, None);
+        assert_eq!(config.local_time, None);
+        assert_eq!(config.whitelist.len(), 1);
+        assert_eq!(config.whitelist[0].id, "user");
+    }
+
+    #[test]
+    fn deserialize_internet_services() {
+        let json = json!({
+            "internet": "connected",
+            "remoteaccess": "disconnected",
+            "time": "not-connected",
+            "swupdate": "not connected"
+        });
+        let services: InternetServices = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            services,
+            InternetServices {
+                internet: ServiceStatus::Connected,
+                remote_access: ServiceStatus::Disconnected,
+                time: ServiceStatus::NotConnected,
+                software_update: ServiceStatus::NotConnected,
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_public_config() {
+        let json = json!({
+            "name": "Philips hue",
+            "datastoreversion": "131",
+            "swversion": "1953188020",
+            "apiversion": "1.53.0",
+            "mac": "00:17:88:00:00:00",
+            "bridgeid": "001788FFFE000000",
+            "factorynew": false,
+            "replacesbridgeid": null,
+            "modelid": "BSB002",
+            "starterkitid": ""
+        });
+        let config: PublicConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(config.bridge_id, "001788FFFE000000");
+        assert_eq!(config.api_version, "1.53.0");
+        assert_eq!(config.parsed_api_version(), (1, 53, 0));
+        assert!(config.api_version_at_least((1, 45, 0)));
+        assert!(!config.api_version_at_least((1, 53, 1)));
+        assert_eq!(config.replaces_bridge_id, None);
+    }
+
+    #[test]
+    fn parse_version() {
+        assert_eq!(super::parse_version("1.45.0"), (1, 45, 0));
+        assert_eq!(super::parse_version("1.3"), (1, 3, 0));
+        assert_eq!(super::parse_version("1.16.0-beta"), (1, 16, 0));
+        assert_eq!(super::parse_version(" 2.x.1 "), (2, 0, 1));
+        assert_eq!(super::parse_version(""), (0, 0, 0));
+    }
+
+    #[test]
+    fn validate_modifier() {
+        let info = json!({"available": 1, "total": 1});
+        let capabilities: Capabilities = serde_json::from_value(json!({
+            "lights": info,
+            "groups": info,
+            "sensors": {"available": 1, "total": 1, "clip": info, "zll": info, "zgp": info},
+            "scenes": {"available": 1, "total": 1, "lightstates": info},
+            "schedules": info,
+            "rules": {"available": 1, "total": 1, "conditions": info, "actions": info},
+            "resourcelinks": info,
+            "streaming": {"available": 1, "total": 1, "channels": 10},
+            "timezones": {"values": ["Europe/Berlin", "UTC"]}
+        }))
+        .unwrap();
+        assert!(Modifier::new().validate(&capabilities).is_ok());
+        let modifier = Modifier::new().with_timezone("Europe/Berlin".into());
+        assert!(modifier.validate(&capabilities).is_ok());
+        let modifier = Modifier::new().with_timezone("Europe/Nowhere".into());
+        assert!(matches!(
+            modifier.validate(&capabilities),
+            Err(Error::InvalidTimezone(_))
+        ));
+        let modifier = Modifier::new().with_zigbee_channel(15);
+        assert!(modifier.validate(&capabilities).is_ok());
+        let modifier = Modifier::new().with_zigbee_channel(12);
+        assert!(matches!(
+            modifier.validate(&capabilities),
+            Err(Error::InvalidZigbeeChannel(12))
+        ));
+    }
 }
@@ -1,10 +1,12 @@
-use crate::resource::{self, Adjust, Alert, Effect};
-use crate::Color;
+use crate::resource::{self, light, Adjust, Alert, CoordinateModifierType, Effect};
+use crate::{color, Color, Error};
 use derive_setters::Setters;
 use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 
 /// A group of lights.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Group {
     /// Identifier of the group.
     #[serde(skip)]
@@ -14,19 +16,29 @@ pub struct Group {
     /// Identifiers of lights that are in this group.
     pub lights: Vec<String>,
     /// Identifiers of sensors that are in this group.
+    #[serde(default)]
     pub sensors: Vec<String>,
     /// Kind of the group.
     #[serde(rename = "type")]
     pub kind: Kind,
     /// Class of the group.
     ///
-    /// Only used if [`kind`] is [`Room`].
+    /// Only used if [`kind`] is [`Room`], [`Zone`] or [`Entertainment`].
     ///
     /// [`kind`]: #structfield.kind
     /// [`Room`]: enum.CreatableKind.html#variant.Room
+    /// [`Zone`]: enum.CreatableKind.html#variant.Zone
+    /// [`Entertainment`]: enum.CreatableKind.html#variant.Entertainment
     pub class: Option<Class>,
     /// State of the group.
     pub state: Option<State>,
+    /// Last state that was sent to all lights of the group.
+    ///
+    /// Attributes that are not supported by the lights of the group are `None`. The group action
+    /// has no reachable attribute, so [`reachable`] is always `true`.
+    ///
+    /// [`reachable`]: light::State::reachable
+    pub action: Option<light::State>,
     /// Model identifier of the group.
     ///
     /// Only present for automatically created luminaires.
@@ -34,18 +46,95 @@ pub struct Group {
     pub model_id: Option<String>,
     /// Unique identifier of the group.
     ///
-    /// In AA:BB:CC:DD format for luminaire groups or AA:BB:CC:DD-XX format for
-    /// lightsource groups, where XX is the lightsource position.
-    #[serde(rename = "unique_id")]
+    /// Only present for automatically created luminaires. In AA:BB:CC:DD format for luminaire
+    /// groups or AA:BB:CC:DD-XX format for lightsource groups, where XX is the lightsource
+    /// position.
+    #[serde(rename = "uniqueid", alias = "unique_id")]
     pub unique_id: Option<String>,
     /// Whether the group is automatically deleted when not referenced anymore.
     pub recycle: Option<bool>,
+    /// Locations of the lights in the group.
+    ///
+    /// Only present if [`kind`] is [`Entertainment`]. The keys are the identifiers of the lights
+    /// and the values are the x, y and z coordinates of the lights, each between -1 and 1.
+    ///
+    /// [`kind`]: #structfield.kind
+    /// [`Entertainment`]: enum.CreatableKind.html#variant.Entertainment
+    pub locations: Option<HashMap<String, [f32; 3]>>,
 }
 
 impl Group {
     pub(crate) fn with_id(self, id: String) -> Self {
         Self { id, ..self }
     }
+
+    /// Returns a modifier that restores the last state that was sent to all lights of the group.
+    ///
+    /// This does not restore the states of single lights that were modified after the state of
+    /// the group. See [`light::State::to_modifier`] for how the color is restored.
+    pub fn snapshot(&self) -> StateModifier {
+        let modifier = match &self.action {
+            Some(v) => v.to_modifier(),
+            None => return StateModifier::new(),
+        };
+        StateModifier {
+            on: modifier.on,
+            brightness: modifier.brightness,
+            hue: modifier.hue,
+            saturation: modifier.saturation,
+            color_space_coordinates: modifier.color_space_coordinates,
+            color_temperature: modifier.color_temperature,
+            effect: modifier.effect,
+            ..StateModifier::default()
+        }
+    }
+
+    /// Returns whether any light in the group is on.
+    ///
+    /// Returns `false` if the group has no state.
+    pub fn any_on(&self) -> bool {
+        self.state.as_ref().is_some_and(|v| v.any_on)
+    }
+
+    /// Returns whether all lights in the group are on.
+    ///
+    /// Returns `false` if the group has no state.
+    pub fn all_on(&self) -> bool {
+        self.state.as_ref().is_some_and(|v| v.all_on)
+    }
+}
+
+// The action and the locations are never NaN, because NaN can't be represented in JSON.
+impl Eq for Group {}
+
+impl Hash for Group {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The action and the locations are skipped, because they contain floats.
+        let Self {
+            id,
+            name,
+            lights,
+            sensors,
+            kind,
+            class,
+            state: group_state,
+            action: _,
+            model_id,
+            unique_id,
+            recycle,
+            locations: _,
+        } = self;
+        id.hash(state);
+        name.hash(state);
+        lights.hash(state);
+        sensors.hash(state);
+        kind.hash(state);
+        class.hash(state);
+        group_state.hash(state);
+        model_id.hash(state);
+        unique_id.hash(state);
+        recycle.hash(state);
+    }
 }
 
 impl resource::Resource for Group {}
@@ -121,6 +210,7 @@ pub enum Class {
     Dining,
     Downstairs,
     Driveway,
+    Free,
     #[serde(rename = "Front door")]
     FrontDoor,
     Garage,
@@ -164,13 +254,21 @@ pub enum Class {
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub struct State {
     /// Whether any light in a group is on.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "crate::util::deserialize_lenient")
+    )]
     pub any_on: bool,
     /// Whether all lights in a group are on.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "crate::util::deserialize_lenient")
+    )]
     pub all_on: bool,
 }
 
 /// Struct for creating a group.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, PartialEq, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Creator {
     /// Sets the name of the group.
@@ -191,6 +289,12 @@ pub struct Creator {
     /// Sets whether the group is automatically deleted when not referenced anymore.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recycle: Option<bool>,
+    /// Sets the locations of the lights of an entertainment group.
+    ///
+    /// The keys are the identifiers of the lights and the values are the x, y and z coordinates of
+    /// the lights, each between -1 and 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locations: Option<HashMap<String, [f32; 3]>>,
 }
 
 impl Creator {
@@ -203,18 +307,63 @@ impl Creator {
             kind: None,
             class: None,
             recycle: None,
+            locations: None,
         }
     }
+
+    /// Checks whether the values of this creator are valid.
+    ///
+    /// Returns [`Error::MissingAttribute`] if the kind is [`CreatableKind::Zone`] and no class is
+    /// set, and [`Error::InvalidLocation`] if a coordinate of a location is not between -1 and 1.
+    ///
+    /// [`Error::MissingAttribute`]: crate::Error::MissingAttribute
+    /// [`Error::InvalidLocation`]: crate::Error::InvalidLocation
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.kind == Some(CreatableKind::Zone) && self.class.is_none() {
+            return Err(Error::MissingAttribute("class"));
+        }
+        validate_locations(self.locations.as_ref())
+    }
+}
+
+// Valid locations are never NaN (see `validate`), so the comparison is reflexive.
+impl Eq for Creator {}
+
+impl Hash for Creator {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The locations are skipped, because they contain floats.
+        let Self {
+            name,
+            lights,
+            sensors,
+            kind,
+            class,
+            recycle,
+            locations: _,
+        } = self;
+        name.hash(state);
+        lights.hash(state);
+        sensors.hash(state);
+        kind.hash(state);
+        class.hash(state);
+        recycle.hash(state);
+    }
 }
 
 impl resource::Creator for Creator {
+    type Required = (String, Vec<String>);
+
+    fn from_required((name, lights): Self::Required) -> Self {
+        Self::new(name, lights)
+    }
+
     fn url_suffix() -> String {
         "groups".to_owned()
     }
 }
 
 /// Struct for modifying group attributes.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct AttributeModifier {
     /// Sets the name of the group.
@@ -229,6 +378,12 @@ pub struct AttributeModifier {
     /// Sets the class of the group.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub class: Option<Class>,
+    /// Sets the locations of the lights of an entertainment group.
+    ///
+    /// The keys are the identifiers of the lights and the values are the x, y and z coordinates of
+    /// the lights, each between -1 and 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locations: Option<HashMap<String, [f32; 3]>>,
 }
 
 impl AttributeModifier {
@@ -236,6 +391,44 @@ impl AttributeModifier {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Checks whether the values of this modifier are valid.
+    ///
+    /// Returns [`Error::InvalidLocation`] if a coordinate of a location is not between -1 and 1.
+    ///
+    /// [`Error::InvalidLocation`]: crate::Error::InvalidLocation
+    pub fn validate(&self) -> crate::Result<()> {
+        validate_locations(self.locations.as_ref())
+    }
+}
+
+fn validate_locations(locations: Option<&HashMap<String, [f32; 3]>>) -> crate::Result<()> {
+    for (id, location) in locations.into_iter().flatten() {
+        if location.iter().any(|v| !(-1.0..=1.0).contains(v)) {
+            return Err(Error::InvalidLocation(id.clone()));
+        }
+    }
+    Ok(())
+}
+
+// Valid locations are never NaN (see `validate`), so the comparison is reflexive.
+impl Eq for AttributeModifier {}
+
+impl Hash for AttributeModifier {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The locations are skipped, because they contain floats.
+        let Self {
+            name,
+            lights,
+            sensors,
+            class,
+            locations: _,
+        } = self;
+        name.hash(state);
+        lights.hash(state);
+        sensors.hash(state);
+        class.hash(state);
+    }
 }
 
 impl resource::Modifier for AttributeModifier {
@@ -246,12 +439,23 @@ impl resource::Modifier for AttributeModifier {
 }
 
 /// Struct for modifying the group state.
+///
+/// The modifier is sent to the action of the group and supports the same attributes as
+/// [`light::StateModifier`], including increments of the color space coordinates and the color
+/// temperature. Additionally a scene can be recalled.
 #[derive(Clone, Debug, Default, PartialEq, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct StateModifier {
     /// Turns the lights on or off.
     pub on: Option<bool>,
     /// Sets the brightness of the lights.
+    ///
+    /// The bridge ignores the brightness and the color of lights that are off, so [`on`] has to
+    /// be set as well to change them (see [`with_brightness_on`] and [`ensure_on`]).
+    ///
+    /// [`on`]: Self::on
+    /// [`with_brightness_on`]: Self::with_brightness_on
+    /// [`ensure_on`]: Self::ensure_on
     pub brightness: Option<Adjust<u8>>,
     /// Sets the hue of the lights.
     pub hue: Option<Adjust<u16>>,
@@ -262,6 +466,10 @@ pub struct StateModifier {
     /// Sets the color temperature of the lights.
     pub color_temperature: Option<Adjust<u16>>,
     /// Sets the alert effect of the lights.
+    ///
+    /// The effect is applied to all lights of the group at once and behaves the same as on a
+    /// single light, e.g. [`Alert::LSelect`] performs breathe cycles for 15 seconds or until it is
+    /// set to [`Alert::None`].
     pub alert: Option<Alert>,
     /// Sets the dynamic effect of the lights.
     pub effect: Option<Effect>,
@@ -269,7 +477,11 @@ pub struct StateModifier {
     ///
     /// This is given as a multiple of 100ms.
     pub transition_time: Option<u16>,
-    /// Sets the scene identifier of the group.
+    /// Recalls the scene with the given identifier on the group.
+    ///
+    /// Combine this with [`transition_time`] to fade into the scene.
+    ///
+    /// [`transition_time`]: Self::transition_time
     pub scene: Option<String>,
 }
 
@@ -279,6 +491,32 @@ impl StateModifier {
         Self::default()
     }
 
+    /// Convenient method to set the [`brightness`] field and turn the lights on.
+    ///
+    /// [`brightness`]: Self::brightness
+    pub fn with_brightness_on(self, value: u8) -> Self {
+        Self {
+            on: Some(true),
+            brightness: Some(Adjust::Override(value)),
+            ..self
+        }
+    }
+
+    /// Turns the lights on if the brightness or color is set and [`on`] is not set.
+    ///
+    /// [`on`]: Self::on
+    pub fn ensure_on(self) -> Self {
+        let sets_color = self.brightness.is_some()
+            || self.hue.is_some()
+            || self.saturation.is_some()
+            || self.color_space_coordinates.is_some()
+            || self.color_temperature.is_some();
+        Self {
+            on: self.on.or(Some(true).filter(|_| sets_color)),
+            ..self
+        }
+    }
+
     /// Convenient method to set the [`color_space_coordinates`] and [`brightness`] fields.
     ///
     /// [`color_space_coordinates`]: Self::color_space_coordinates
@@ -293,6 +531,39 @@ impl StateModifier {
         }
         modifier
     }
+
+    /// Convenient method to adjust the [`color_space_coordinates`] field in the given directions.
+    ///
+    /// [`color_space_coordinates`]: Self::color_space_coordinates
+    pub fn with_color_space_coordinates_modify(
+        self,
+        kind: CoordinateModifierType,
+        value: (f32, f32),
+    ) -> Self {
+        Self {
+            color_space_coordinates: Some(kind.adjust(value)),
+            ..self
+        }
+    }
+
+    /// Convenient method to set the [`color_temperature`] and [`color_space_coordinates`] fields
+    /// to a white tone by its name.
+    ///
+    /// See [`color::NAMED_COLORS`] for the available names. Returns [`Error::NameNotFound`] if
+    /// no white tone with the given name exists.
+    ///
+    /// [`color_temperature`]: Self::color_temperature
+    /// [`color_space_coordinates`]: Self::color_space_coordinates
+    /// [`color::NAMED_COLORS`]: crate::color::NAMED_COLORS
+    /// [`Error::NameNotFound`]: crate::Error::NameNotFound
+    pub fn with_color_named(self, name: &str) -> crate::Result<Self> {
+        let color = color::named(name).ok_or_else(|| Error::NameNotFound(name.to_owned()))?;
+        Ok(Self {
+            color_temperature: Some(Adjust::Override(color.color_temperature)),
+            color_space_coordinates: Some(Adjust::Override(color.space_coordinates)),
+            ..self
+        })
+    }
 }
 
 impl resource::Modifier for StateModifier {
@@ -331,6 +602,7 @@ impl Serialize for StateModifier {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::resource::ColorMode;
     use serde_json::json;
 
     #[test]
@@ -350,6 +622,7 @@ mod tests {
             kind: Some(CreatableKind::Room),
             class: Some(Class::Office),
             recycle: Some(true),
+            locations: Some(vec![("1".into(), [0.5, -0.5, 0.0])].into_iter().collect()),
         };
         let creator_json = serde_json::to_value(creator).unwrap();
         let expected_json = json!({
@@ -358,11 +631,133 @@ mod tests {
             "sensors": ["3"],
             "type": "Room",
             "class": "Office",
-            "recycle": true
+            "recycle": true,
+            "locations": {"1": [0.5, -0.5, 0.0]}
+        });
+        assert_eq!(creator_json, expected_json);
+    }
+
+    #[test]
+    fn zone_creator() {
+        // Zones can contain lights that are also in rooms, so any lights are accepted.
+        let creator = Creator::new("test".into(), vec!["1".into(), "5".into()])
+            .with_kind(CreatableKind::Zone);
+        assert!(matches!(
+            creator.validate(),
+            Err(Error::MissingAttribute("class"))
+        ));
+        let creator = creator.with_class(Class::Downstairs);
+        assert!(creator.validate().is_ok());
+        let creator_json = serde_json::to_value(creator).unwrap();
+        let expected_json = json!({
+            "name": "test",
+            "lights": ["1", "5"],
+            "type": "Zone",
+            "class": "Downstairs"
         });
         assert_eq!(creator_json, expected_json);
     }
 
+    #[test]
+    fn validate_locations() {
+        let creator = Creator::new("test".into(), vec!["1".into()]);
+        assert!(creator.validate().is_ok());
+        let creator =
+            creator.with_locations(vec![("1".into(), [1.0, -1.0, 0.0])].into_iter().collect());
+        assert!(creator.validate().is_ok());
+
+        let modifier = AttributeModifier::new()
+            .with_locations(vec![("2".into(), [0.0, 1.5, 0.0])].into_iter().collect());
+        assert!(matches!(
+            modifier.validate(),
+            Err(Error::InvalidLocation(id)) if id == "2"
+        ));
+    }
+
+    #[test]
+    fn deserialize_group() {
+        let json = json!({
+            "name": "Living room",
+            "lights": ["1", "2"],
+            "type": "Room",
+            "class": "Living room",
+            "state": {"all_on": false, "any_on": true},
+            "action": {"on": true, "bri": 254, "alert": "none"}
+        });
+        let group: Group = serde_json::from_value(json).unwrap();
+        assert_eq!(group.kind, Kind::Creatable(CreatableKind::Room));
+        assert!(group.any_on());
+        assert!(!group.all_on());
+        assert!(group.sensors.is_empty());
+        let modifier_json = serde_json::to_value(group.snapshot()).unwrap();
+        assert_eq!(modifier_json, json!({"on": true, "bri": 254}));
+    }
+
+    #[test]
+    fn deserialize_group_action() {
+        let json = json!({
+            "name": "Kitchen",
+            "lights": ["3"],
+            "type": "Room",
+            "action": {
+                "on": false,
+                "bri": 127,
+                "hue": 8418,
+                "sat": 140,
+                "effect": "none",
+                "xy": [0.4573, 0.41],
+                "ct": 366,
+                "alert": "select",
+                "colormode": "ct"
+            }
+        });
+        let group: Group = serde_json::from_value(json).unwrap();
+        let action = group.action.unwrap();
+        assert_eq!(action.on, Some(false));
+        assert_eq!(action.brightness, Some(127));
+        assert_eq!(action.hue, Some(8418));
+        assert_eq!(action.saturation, Some(140));
+        assert_eq!(action.color_space_coordinates, Some((0.4573, 0.41)));
+        assert_eq!(action.color_temperature, Some(366));
+        assert_eq!(action.effect, Some(Effect::None));
+        assert_eq!(action.color_mode, Some(ColorMode::ColorTemperature));
+        assert!(action.reachable);
+    }
+
+    #[test]
+    fn deserialize_luminaire_group() {
+        let json = json!({
+            "name": "Hue Beyond",
+            "lights": ["4", "5"],
+            "type": "Lightsource",
+            "modelid": "HBL001",
+            "uniqueid": "00:17:88:01-02",
+            "state": {"all_on": false, "any_on": true}
+        });
+        let group: Group = serde_json::from_value(json).unwrap();
+        assert_eq!(group.kind, Kind::Immutable(ImmutableKind::LightSource));
+        assert_eq!(group.model_id, Some("HBL001".into()));
+        assert_eq!(group.unique_id, Some("00:17:88:01-02".into()));
+    }
+
+    #[test]
+    fn deserialize_entertainment_group() {
+        let json = json!({
+            "name": "TV area",
+            "lights": ["1", "2"],
+            "type": "Entertainment",
+            "class": "TV",
+            "state": {"all_on": true, "any_on": true},
+            "locations": {"1": [-0.5, 0.8, 0.0], "2": [0.5, 0.8, 0.0]}
+        });
+        let group: Group = serde_json::from_value(json).unwrap();
+        assert_eq!(group.kind, Kind::Creatable(CreatableKind::Entertainment));
+        assert_eq!(group.class, Some(Class::Tv));
+        let locations = group.locations.unwrap();
+        assert_eq!(locations.len(), 2);
+        assert_eq!(locations["1"], [-0.5, 0.8, 0.0]);
+    }
+
     #[test]
     fn serialize_attribute_modifier() {
         let modifier = AttributeModifier::new();
@@ -375,6 +770,7 @@ mod tests {
             lights: Some(vec!["1".into(), "2".into()]),
             sensors: Some(vec!["3".into()]),
             class: Some(Class::Office),
+            locations: None,
         };
         let modifier_json = serde_json::to_value(modifier).unwrap();
         let expected_json = json!({
@@ -384,6 +780,11 @@ mod tests {
             "class": "Office"
         });
         assert_eq!(modifier_json, expected_json);
+
+        let modifier = AttributeModifier::new().with_lights(Vec::new());
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({"lights": []});
+        assert_eq!(modifier_json, expected_json);
     }
 
     #[test]
@@ -428,5 +829,32 @@ mod tests {
             "xy": [0.0, 0.0]
         });
         assert_eq!(modifier_json, expected_json);
+
+        let modifier = StateModifier::new()
+            .with_hue(Adjust::Decrement(1000))
+            .with_color_space_coordinates(Adjust::Increment((0.5, 0.25)))
+            .with_color_temperature(Adjust::Increment(50));
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({
+            "hue_inc": -1000,
+            "xy_inc": [0.5, 0.25],
+            "ct_inc": 50
+        });
+        assert_eq!(modifier_json, expected_json);
+
+        let modifier = StateModifier::new().with_alert(Alert::LSelect);
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({"alert": "lselect"});
+        assert_eq!(modifier_json, expected_json);
+
+        let modifier = StateModifier::new()
+            .with_scene("AbCdEfGhIjKlMnO".into())
+            .with_transition_time(10);
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({
+            "scene": "AbCdEfGhIjKlMnO",
+            "transitiontime": 10
+        });
+        assert_eq!(modifier_json, expected_json);
     }
 }
@@ -1,9 +1,12 @@
 #![allow(clippy::needless_update)]
 
-use crate::resource::{self, Adjust, Alert, ColorMode, Effect};
-use crate::Color;
+use crate::resource::{self, Adjust, Alert, ColorMode, CoordinateModifierType, Effect};
+use crate::{color, util, Color, Error};
 use derive_setters::Setters;
-use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value as JsonValue;
+use std::convert::TryFrom;
+use std::ops::RangeInclusive;
 
 /// A light.
 #[derive(Clone, Debug, PartialEq, Deserialize)]
@@ -15,14 +18,16 @@ pub struct Light {
     pub name: String,
     /// Type of the light.
     #[serde(rename = "type")]
-    pub kind: String,
+    pub kind: Kind,
     /// Current state of the light.
     pub state: State,
     /// The hardware model of the light.
     #[serde(rename = "modelid")]
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub model_id: String,
     /// Unique ID of the light.
     #[serde(rename = "uniqueid")]
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub unique_id: String,
     /// Product ID of the light.
     #[serde(rename = "productid")]
@@ -35,51 +40,324 @@ pub struct Light {
     pub manufacturer_name: Option<String>,
     /// The software version running on the light.
     #[serde(rename = "swversion")]
+    #[cfg_attr(feature = "lenient", serde(default))]
     pub software_version: String,
     /// Information about software updates of the light.
+    ///
+    /// Not available on older bridges.
     #[cfg(not(feature = "old-api"))]
     #[serde(rename = "swupdate")]
-    pub software_update: SoftwareUpdate,
+    pub software_update: Option<SoftwareUpdate>,
     /// Configuration of the light.
+    ///
+    /// Not available on older bridges.
     #[cfg(not(feature = "old-api"))]
-    pub config: Config,
+    pub config: Option<Config>,
     /// Capabilities of the light.
+    ///
+    /// Not available on older bridges.
     #[cfg(not(feature = "old-api"))]
-    pub capabilities: Capabilities,
+    pub capabilities: Option<Capabilities>,
 }
 
 impl Light {
     pub(crate) fn with_id(self, id: String) -> Self {
         Self { id, ..self }
     }
+
+    /// Returns whether the light can be reached by the bridge.
+    pub fn is_reachable(&self) -> bool {
+        self.state.reachable
+    }
+
+    /// Returns the changes of the state from this light to another snapshot of the light.
+    pub fn diff(&self, other: &Light) -> LightDiff {
+        fn changed<T: Copy + PartialEq>(old: T, new: T) -> Option<(T, T)> {
+            if old == new {
+                None
+            } else {
+                Some((old, new))
+            }
+        }
+        let (old, new) = (&self.state, &other.state);
+        LightDiff {
+            on: changed(old.on, new.on),
+            brightness: changed(old.brightness, new.brightness),
+            hue: changed(old.hue, new.hue),
+            saturation: changed(old.saturation, new.saturation),
+            color_space_coordinates: changed(
+                old.color_space_coordinates,
+                new.color_space_coordinates,
+            ),
+            color_temperature: changed(old.color_temperature, new.color_temperature),
+            reachable: changed(old.reachable, new.reachable),
+            alert: changed(old.alert, new.alert),
+            effect: changed(old.effect, new.effect),
+        }
+    }
+
+    /// Returns the color gamut of the light.
+    ///
+    /// Returns `None` if the light does not report its capabilities or the coordinates of its
+    /// color gamut.
+    #[cfg(not(feature = "old-api"))]
+    pub fn color_gamut(&self) -> Option<ColorGamut> {
+        let control = &self.capabilities.as_ref()?.control;
+        match control.color_gamut.as_deref() {
+            Some(&[red, green, blue]) => Some(ColorGamut {
+                kind: control.color_gamut_type.unwrap_or(ColorGamutType::Other),
+                red,
+                green,
+                blue,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the range of color temperatures in mired that is supported by the light.
+    ///
+    /// If the light does not report the range in its capabilities, the range of the Philips Hue
+    /// API (`153..=500`) is returned.
+    pub fn color_temperature_range(&self) -> RangeInclusive<u16> {
+        #[cfg(not(feature = "old-api"))]
+        if let Some(range) = self
+            .capabilities
+            .as_ref()
+            .and_then(|v| v.control.color_temperature.as_ref())
+        {
+            let to_u16 = |v: usize| u16::try_from(v).unwrap_or(u16::MAX);
+            let (min, max) = (to_u16(range.min), to_u16(range.max));
+            return min.min(max)..=max.max(min);
+        }
+        153..=500
+    }
+
+    /// Clamps a color temperature in mired into the range that is supported by the light.
+    ///
+    /// See [`color_temperature_range`] for the range that is used.
+    ///
+    /// [`color_temperature_range`]: Self::color_temperature_range
+    pub fn clamp_color_temperature(&self, value: u16) -> u16 {
+        let range = self.color_temperature_range();
+        value.clamp(*range.start(), *range.end())
+    }
+
+    /// Returns the lowest brightness that the light can display.
+    ///
+    /// This is derived from the minimal dim level in the capabilities of the light, which is the
+    /// lowest light output in thousandths of a percent. If the light does not report it, 1 is
+    /// returned.
+    pub fn min_brightness(&self) -> u8 {
+        #[cfg(not(feature = "old-api"))]
+        if let Some(level) = self
+            .capabilities
+            .as_ref()
+            .and_then(|v| v.control.min_dimlevel)
+        {
+            return (level as f64 / 100_000.0 * 254.0).ceil().clamp(1.0, 254.0) as u8;
+        }
+        1
+    }
+
+    /// Converts a brightness in percent into a brightness of the light.
+    ///
+    /// 1% is mapped to the [`min_brightness`] and 100% to the maximal brightness of 254. Values
+    /// greater than 100 are treated as 100 and 0 is treated as 1.
+    ///
+    /// [`min_brightness`]: Self::min_brightness
+    pub fn brightness_from_percent(&self, percent: u8) -> u8 {
+        let min = f64::from(self.min_brightness());
+        let percent = f64::from(percent.clamp(1, 100));
+        (min + (254.0 - min) * (percent - 1.0) / 99.0).round() as u8
+    }
+
+    /// Returns whether the light supports setting a color.
+    ///
+    /// For unknown kinds of lights this is derived from the state of the light.
+    pub fn supports_color(&self) -> bool {
+        match self.kind {
+            Kind::ExtendedColorLight | Kind::ColorLight => true,
+            Kind::Other(_) => self.state.color_space_coordinates.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Returns whether the light supports setting a color temperature.
+    ///
+    /// For unknown kinds of lights this is derived from the state of the light.
+    pub fn supports_color_temperature(&self) -> bool {
+        match self.kind {
+            Kind::ExtendedColorLight | Kind::ColorTemperatureLight => true,
+            Kind::Other(_) => self.state.color_temperature.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Returns whether the light supports setting the brightness.
+    ///
+    /// For unknown kinds of lights this is derived from the state of the light.
+    pub fn supports_dimming(&self) -> bool {
+        match self.kind {
+            Kind::OnOffLight | Kind::OnOffPlugInUnit => false,
+            Kind::Other(_) => self.state.brightness.is_some(),
+            _ => true,
+        }
+    }
+
+    /// Returns whether the light is on.
+    ///
+    /// Lights that do not report an on state are treated as off.
+    pub fn is_on(&self) -> bool {
+        self.state.on.unwrap_or(false)
+    }
+
+    /// Returns a modifier that restores the current state of the light.
+    ///
+    /// If the light is not reachable, the last known state of the light is used. See
+    /// [`State::to_modifier`] for how the color is restored.
+    pub fn snapshot(&self) -> StateModifier {
+        self.state.to_modifier().for_light(self)
+    }
+
+    /// Returns the current color space coordinates of the light.
+    ///
+    /// Returns `None` if the light does not support colors.
+    pub fn current_xy(&self) -> Option<(f32, f32)> {
+        self.state
+            .color_space_coordinates
+            .filter(|_| self.supports_color())
+    }
+
+    /// Returns the current hue and saturation of the light.
+    ///
+    /// Returns `None` if the light does not support colors.
+    pub fn current_hue_sat(&self) -> Option<(u16, u8)> {
+        match (self.state.hue, self.state.saturation) {
+            (Some(hue), Some(saturation)) if self.supports_color() => Some((hue, saturation)),
+            _ => None,
+        }
+    }
+
+    /// Returns the current color temperature of the light.
+    ///
+    /// Returns `None` if the light does not support color temperatures.
+    pub fn current_ct(&self) -> Option<u16> {
+        self.state
+            .color_temperature
+            .filter(|_| self.supports_color_temperature())
+    }
+
+    /// Returns approximate rgb values of the color that is currently emitted by the light.
+    ///
+    /// The color is approximated from the color space coordinates or the color temperature,
+    /// depending on the current color mode, and the brightness of the light (see
+    /// [`color::space_coordinates_to_rgb`] and [`color::color_temperature_to_rgb`]). Whether the
+    /// light is turned on is not taken into account.
+    ///
+    /// Returns `None` if the light supports neither colors nor color temperatures.
+    pub fn current_rgb(&self) -> Option<(u8, u8, u8)> {
+        let brightness = self.state.brightness.unwrap_or(254);
+        let xy = self.current_xy();
+        let ct = self.current_ct();
+        match (self.current_color_mode(), xy, ct) {
+            (Some(ColorMode::ColorTemperature), _, Some(ct)) | (_, None, Some(ct)) => {
+                Some(color::color_temperature_to_rgb(ct, brightness))
+            }
+            (_, Some((x, y)), _) => Some(color::space_coordinates_to_rgb(x, y, brightness)),
+            _ => None,
+        }
+    }
+
+    /// Returns the color mode that is currently used by the light.
+    ///
+    /// Returns `None` if the light supports neither colors nor color temperatures.
+    pub fn current_color_mode(&self) -> Option<ColorMode> {
+        self.state
+            .color_mode
+            .filter(|_| self.supports_color() || self.supports_color_temperature())
+    }
 }
 
 impl resource::Resource for Light {}
 
+/// Kind of a light.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(from = "String")]
+pub enum Kind {
+    /// A light that supports color and color temperature.
+    ExtendedColorLight,
+    /// A light that supports color.
+    ColorLight,
+    /// A light that supports color temperature.
+    ColorTemperatureLight,
+    /// A light that only supports brightness.
+    DimmableLight,
+    /// A light that can only be turned on and off.
+    OnOffLight,
+    /// A plug-in unit that can only be turned on and off.
+    OnOffPlugInUnit,
+    /// Any other kind of light.
+    Other(String),
+}
+
+impl From<String> for Kind {
+    fn from(value: String) -> Self {
+        match value.to_lowercase().as_str() {
+            "extended color light" => Self::ExtendedColorLight,
+            "color light" => Self::ColorLight,
+            "color temperature light" => Self::ColorTemperatureLight,
+            "dimmable light" => Self::DimmableLight,
+            "on/off light" => Self::OnOffLight,
+            "on/off plug-in unit" => Self::OnOffPlugInUnit,
+            _ => Self::Other(value),
+        }
+    }
+}
+
 /// State of a light.
 #[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct State {
     /// Whether the light is on.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub on: Option<bool>,
     /// Brightness of the light.
     ///
     /// The maximum brightness is 254 and 1 is the minimum brightness.
     #[serde(rename = "bri")]
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub brightness: Option<u8>,
     /// Hue of the light.
     ///
     /// Both 0 and 65535 are red, 25500 is green and 46920 is blue.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub hue: Option<u16>,
     /// Saturation of the light.
     ///
     /// The most saturated (colored) is 254 and 0 is the least saturated (white).
     #[serde(rename = "sat")]
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub saturation: Option<u8>,
     /// X and y coordinates of a color in CIE color space. Both values must be between 0 and 1.
     #[serde(rename = "xy")]
     pub color_space_coordinates: Option<(f32, f32)>,
     /// Mired color temperature of the light.
     #[serde(rename = "ct")]
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub color_temperature: Option<u16>,
     /// Alert effect of the light.
     pub alert: Option<Alert>,
@@ -89,16 +367,107 @@ pub struct State {
     #[serde(rename = "colormode")]
     pub color_mode: Option<ColorMode>,
     /// Whether the light can be reached by the bridge.
+    ///
+    /// Some lights do not report this attribute, in which case they are assumed to be reachable.
+    #[serde(default = "default_reachable")]
+    #[cfg_attr(
+        feature = "lenient",
+        serde(deserialize_with = "util::deserialize_lenient")
+    )]
     pub reachable: bool,
 }
 
+fn default_reachable() -> bool {
+    true
+}
+
+/// Old and new value of an attribute that changed.
+pub type Change<T> = (T, T);
+
+/// Changes of the state between two snapshots of a light.
+///
+/// Every field contains the old and the new value if the attribute changed. Returned by
+/// [`Light::diff`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LightDiff {
+    /// Change of whether the light is on.
+    pub on: Option<Change<Option<bool>>>,
+    /// Change of the brightness.
+    pub brightness: Option<Change<Option<u8>>>,
+    /// Change of the hue.
+    pub hue: Option<Change<Option<u16>>>,
+    /// Change of the saturation.
+    pub saturation: Option<Change<Option<u8>>>,
+    /// Change of the color space coordinates.
+    pub color_space_coordinates: Option<Change<Option<(f32, f32)>>>,
+    /// Change of the color temperature.
+    pub color_temperature: Option<Change<Option<u16>>>,
+    /// Change of whether the light can be reached by the bridge.
+    pub reachable: Option<Change<bool>>,
+    /// Change of the alert effect.
+    pub alert: Option<Change<Option<Alert>>>,
+    /// Change of the dynamic effect.
+    pub effect: Option<Change<Option<Effect>>>,
+}
+
+impl LightDiff {
+    /// Returns whether no attribute changed.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl State {
+    /// Returns a modifier that sets a light to this state.
+    ///
+    /// Only the attributes of the current color mode are set, so that the color is restored in
+    /// the same way as it was set. If the color mode is unknown, the color space coordinates or
+    /// the color temperature are used.
+    pub fn to_modifier(&self) -> StateModifier {
+        let mut modifier = StateModifier {
+            on: self.on,
+            brightness: self.brightness.map(Adjust::Override),
+            effect: self.effect,
+            ..StateModifier::default()
+        };
+        let color_mode = self.color_mode.or_else(|| {
+            if self.color_space_coordinates.is_some() {
+                Some(ColorMode::ColorSpaceCoordinates)
+            } else if self.color_temperature.is_some() {
+                Some(ColorMode::ColorTemperature)
+            } else {
+                None
+            }
+        });
+        match color_mode {
+            Some(ColorMode::ColorSpaceCoordinates) => {
+                modifier.color_space_coordinates =
+                    self.color_space_coordinates.map(Adjust::Override);
+            }
+            Some(ColorMode::ColorTemperature) => {
+                modifier.color_temperature = self.color_temperature.map(Adjust::Override);
+            }
+            Some(ColorMode::HueAndSaturation) => {
+                modifier.hue = self.hue.map(Adjust::Override);
+                modifier.saturation = self.saturation.map(Adjust::Override);
+            }
+            None => {}
+        }
+        modifier
+    }
+}
+
 /// Information about software updates of a light.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub struct SoftwareUpdate {
     /// State of software updates.
     pub state: SoftwareUpdateState,
     /// When the last update was installed.
-    #[serde(rename = "lastinstall")]
+    #[serde(
+        rename = "lastinstall",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
     pub last_install: Option<chrono::NaiveDateTime>,
 }
 
@@ -108,8 +477,8 @@ pub struct SoftwareUpdate {
 ///
 /// [this issue]: https://github.com/yuqio/huelib-rs/issues/1
 #[non_exhaustive]
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(from = "String")]
 pub enum SoftwareUpdateState {
     /// No updates are available.
     NoUpdates,
@@ -119,11 +488,36 @@ pub enum SoftwareUpdateState {
     Transferring,
     /// Device is ready to install new updates.
     ReadyToInstall,
-    // FIXME: Add missing variants for states (https://github.com/yuqio/huelib-rs/issues/1)
+    /// Device is installing new updates.
+    Installing,
+    /// The battery of the device is too low to install new updates.
+    BatteryLow,
+    /// Device rejected the downloaded update.
+    ImageRejected,
+    /// An error occurred while updating the device.
+    Error,
+    /// A state that is not known by this library.
+    Other(String),
+}
+
+impl From<String> for SoftwareUpdateState {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "noupdates" => Self::NoUpdates,
+            "notupdatable" => Self::NotUpdatable,
+            "transferring" => Self::Transferring,
+            "readytoinstall" => Self::ReadyToInstall,
+            "installing" => Self::Installing,
+            "batterylow" => Self::BatteryLow,
+            "imagerejected" => Self::ImageRejected,
+            "error" => Self::Error,
+            _ => Self::Other(value),
+        }
+    }
 }
 
 /// Configuration of a light.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Config {
     /// Arche type of the light.
     #[serde(rename = "archetype")]
@@ -137,12 +531,78 @@ pub struct Config {
 }
 
 /// Startup configuration of a light.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct StartupConfig {
     /// Mode of the startup.
-    pub mode: String,
+    pub mode: StartupMode,
     /// Whether startup is configured for the light.
     pub configured: bool,
+    /// State of the light after power is restored, if the mode is [`StartupMode::Custom`].
+    #[serde(rename = "customsettings")]
+    pub custom_settings: Option<StartupSettings>,
+}
+
+/// Behavior of a light when power is restored.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum StartupMode {
+    /// The light turns on with a bright warm white.
+    Safety,
+    /// The light restores its state from before the power was lost, or stays off if it was
+    /// switched off before.
+    PowerFail,
+    /// The light restores the last state in which it was on.
+    LastOnState,
+    /// The light turns on with custom settings.
+    Custom,
+    /// A mode that is not known by this library.
+    Other(String),
+}
+
+impl From<String> for StartupMode {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "safety" => Self::Safety,
+            "powerfail" => Self::PowerFail,
+            "lastonstate" => Self::LastOnState,
+            "custom" => Self::Custom,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+impl From<StartupMode> for String {
+    fn from(value: StartupMode) -> Self {
+        match value {
+            StartupMode::Safety => "safety".to_owned(),
+            StartupMode::PowerFail => "powerfail".to_owned(),
+            StartupMode::LastOnState => "lastonstate".to_owned(),
+            StartupMode::Custom => "custom".to_owned(),
+            StartupMode::Other(v) => v,
+        }
+    }
+}
+
+/// State of a light after power is restored, used by [`StartupMode::Custom`].
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct StartupSettings {
+    /// Brightness of the light.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "bri")]
+    pub brightness: Option<u8>,
+    /// Color space coordinates of the light.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "xy")]
+    pub color_space_coordinates: Option<(f32, f32)>,
+    /// Color temperature of the light.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "ct")]
+    pub color_temperature: Option<u16>,
+}
+
+impl StartupSettings {
+    /// Creates a new [`StartupSettings`].
+    pub fn new() -> Self {
+        Self::default()
+    }
 }
 
 /// Capabilities of a light.
@@ -170,12 +630,106 @@ pub struct ControlCapabilities {
     pub color_gamut: Option<Vec<(f32, f32)>>,
     /// Type of the color gamut of the light.
     #[serde(rename = "colorgamuttype")]
-    pub color_gamut_type: Option<String>,
+    pub color_gamut_type: Option<ColorGamutType>,
     /// Maximal/minimal color temperature of the light.
     #[serde(rename = "ct")]
     pub color_temperature: Option<ColorTemperatureCapabilities>,
 }
 
+/// Type of the color gamut of a light.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
+pub enum ColorGamutType {
+    /// Gamut of older LivingColors lights.
+    A,
+    /// Gamut of older Hue bulbs.
+    B,
+    /// Gamut of newer Hue bulbs.
+    C,
+    /// Any other gamut.
+    #[serde(rename = "other", other)]
+    Other,
+}
+
+/// Color gamut of a light.
+///
+/// The gamut is a triangle in the CIE color space that contains all colors that can be displayed
+/// by the light.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorGamut {
+    /// Type of the color gamut.
+    pub kind: ColorGamutType,
+    /// Color space coordinates of the red corner.
+    pub red: (f32, f32),
+    /// Color space coordinates of the green corner.
+    pub green: (f32, f32),
+    /// Color space coordinates of the blue corner.
+    pub blue: (f32, f32),
+}
+
+impl ColorGamut {
+    /// Returns whether the color space coordinates are inside of the gamut.
+    pub fn contains(&self, (x, y): (f32, f32)) -> bool {
+        let cross =
+            |a: (f32, f32), b: (f32, f32)| (b.0 - a.0) * (y - a.1) - (b.1 - a.1) * (x - a.0);
+        let d1 = cross(self.red, self.green);
+        let d2 = cross(self.green, self.blue);
+        let d3 = cross(self.blue, self.red);
+        let has_negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+        let has_positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+        !(has_negative && has_positive)
+    }
+
+    /// Returns the color space coordinates inside of the gamut that are closest to the given
+    /// coordinates.
+    pub fn clamp(&self, xy: (f32, f32)) -> (f32, f32) {
+        if self.contains(xy) {
+            return xy;
+        }
+        let closest_on_edge = |a: (f32, f32), b: (f32, f32)| {
+            let ab = (b.0 - a.0, b.1 - a.1);
+            let t = ((xy.0 - a.0) * ab.0 + (xy.1 - a.1) * ab.1) / (ab.0 * ab.0 + ab.1 * ab.1);
+            let t = t.clamp(0.0, 1.0);
+            (a.0 + ab.0 * t, a.1 + ab.1 * t)
+        };
+        let distance = |p: (f32, f32)| (p.0 - xy.0).powi(2) + (p.1 - xy.1).powi(2);
+        let candidates = [
+            closest_on_edge(self.red, self.green),
+            closest_on_edge(self.green, self.blue),
+            closest_on_edge(self.blue, self.red),
+        ];
+        let mut closest = candidates[0];
+        for candidate in &candidates[1..] {
+            if distance(*candidate) < distance(closest) {
+                closest = *candidate;
+            }
+        }
+        closest
+    }
+
+    /// Returns the complementary color of the given color space coordinates, clamped into the
+    /// gamut.
+    ///
+    /// The hue of the color is rotated by 180 degrees (see [`color::rotate_hue`]).
+    ///
+    /// [`color::rotate_hue`]: crate::color::rotate_hue
+    pub fn complementary(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        self.clamp(color::rotate_hue(x, y, 180.0))
+    }
+
+    /// Returns analogous colors of the given color space coordinates, clamped into the gamut.
+    ///
+    /// The hues of the returned colors are `step` degrees apart and centered on the hue of the
+    /// given color, which is the middle color if `count` is odd. For example a count of 3 and a
+    /// step of 30 returns the colors rotated by -30, 0 and 30 degrees. This can be used to
+    /// distribute a palette of colors across the lights of a group.
+    pub fn analogous(&self, (x, y): (f32, f32), count: usize, step: f32) -> Vec<(f32, f32)> {
+        let center = (count as f32 - 1.0) / 2.0;
+        (0..count)
+            .map(|i| self.clamp(color::rotate_hue(x, y, (i as f32 - center) * step)))
+            .collect()
+    }
+}
+
 /// Color temperature capabilities of a light.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub struct ColorTemperatureCapabilities {
@@ -217,6 +771,65 @@ impl resource::Modifier for AttributeModifier {
     }
 }
 
+/// Modifier for the light configuration.
+///
+/// Not all lights support configuring the startup behavior, the bridge returns an error for
+/// lights that do not support it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct ConfigModifier {
+    /// Sets the arche type of the light.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "archetype")]
+    pub arche_type: Option<String>,
+    /// Sets the function of the light.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    /// Sets the direction of the light.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<String>,
+    /// Sets the behavior of the light when power is restored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub startup: Option<StartupModifier>,
+}
+
+impl ConfigModifier {
+    /// Creates a new [`ConfigModifier`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl resource::Modifier for ConfigModifier {
+    type Id = String;
+    fn url_suffix(id: Self::Id) -> String {
+        format!("lights/{}/config", id)
+    }
+}
+
+/// Modifier for the startup behavior of a light.
+#[derive(Clone, Debug, PartialEq, Serialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct StartupModifier {
+    /// Sets the mode of the startup.
+    #[setters(skip)]
+    pub mode: StartupMode,
+    /// Sets the state of the light after power is restored.
+    ///
+    /// Only used if the mode is [`StartupMode::Custom`].
+    #[serde(skip_serializing_if = "Option::is_none", rename = "customsettings")]
+    pub custom_settings: Option<StartupSettings>,
+}
+
+impl StartupModifier {
+    /// Creates a new [`StartupModifier`].
+    pub fn new(mode: StartupMode) -> Self {
+        Self {
+            mode,
+            custom_settings: None,
+        }
+    }
+}
+
 /// Static modifier for the light state.
 ///
 /// In comparison to [`StateModifier`], this modifier cannot increment/decrement any attributes or
@@ -226,7 +839,7 @@ impl resource::Modifier for AttributeModifier {
 ///
 /// [`scene::Modifier`]: super::scene::Modifier
 /// [`scene::Creator`]: super::scene::Creator
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Setters)]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct StaticStateModifier {
     /// Turns the light on or off.
@@ -287,12 +900,25 @@ impl resource::Modifier for StaticStateModifier {
 }
 
 /// Modifier for the light state.
+///
+/// The modifier can be stored as JSON and restored with [`TryFrom<JsonValue>`] or by
+/// deserializing it, which accepts the same attributes that are sent to the bridge. The increment
+/// of the color space coordinates is a pair of signed values, so it is always restored as
+/// [`Adjust::Increment`], e.g. `Adjust::Decrement((0.1, 0.2))` as
+/// `Adjust::Increment((-0.1, -0.2))`. Both send the same request to the bridge.
 #[derive(Clone, Debug, Default, PartialEq, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct StateModifier {
     /// Turns the light on or off.
     pub on: Option<bool>,
     /// Sets the brightness of the light.
+    ///
+    /// The bridge ignores the brightness and the color of a light that is off, so [`on`] has to be
+    /// set as well to change them (see [`with_brightness_on`] and [`ensure_on`]).
+    ///
+    /// [`on`]: Self::on
+    /// [`with_brightness_on`]: Self::with_brightness_on
+    /// [`ensure_on`]: Self::ensure_on
     pub brightness: Option<Adjust<u8>>,
     /// Sets the hue of a light.
     pub hue: Option<Adjust<u16>>,
@@ -318,6 +944,32 @@ impl StateModifier {
         Self::default()
     }
 
+    /// Convenient method to set the [`brightness`] field and turn the light on.
+    ///
+    /// [`brightness`]: Self::brightness
+    pub fn with_brightness_on(self, value: u8) -> Self {
+        Self {
+            on: Some(true),
+            brightness: Some(Adjust::Override(value)),
+            ..self
+        }
+    }
+
+    /// Turns the light on if the brightness or color is set and [`on`] is not set.
+    ///
+    /// [`on`]: Self::on
+    pub fn ensure_on(self) -> Self {
+        let sets_color = self.brightness.is_some()
+            || self.hue.is_some()
+            || self.saturation.is_some()
+            || self.color_space_coordinates.is_some()
+            || self.color_temperature.is_some();
+        Self {
+            on: self.on.or(Some(true).filter(|_| sets_color)),
+            ..self
+        }
+    }
+
     /// Convenient method to set the [`color_space_coordinates`] and [`brightness`] fields.
     ///
     /// [`color_space_coordinates`]: Self::color_space_coordinates
@@ -332,6 +984,96 @@ impl StateModifier {
         }
         modifier
     }
+
+    /// Convenient method to adjust the [`color_space_coordinates`] field in the given directions.
+    ///
+    /// [`color_space_coordinates`]: Self::color_space_coordinates
+    pub fn with_color_space_coordinates_modify(
+        self,
+        kind: CoordinateModifierType,
+        value: (f32, f32),
+    ) -> Self {
+        Self {
+            color_space_coordinates: Some(kind.adjust(value)),
+            ..self
+        }
+    }
+
+    /// Convenient method to set the [`color_temperature`] and [`color_space_coordinates`] fields
+    /// to a white tone by its name.
+    ///
+    /// See [`color::NAMED_COLORS`] for the available names. Returns [`Error::NameNotFound`] if
+    /// no white tone with the given name exists.
+    ///
+    /// [`color_temperature`]: Self::color_temperature
+    /// [`color_space_coordinates`]: Self::color_space_coordinates
+    /// [`color::NAMED_COLORS`]: crate::color::NAMED_COLORS
+    /// [`Error::NameNotFound`]: crate::Error::NameNotFound
+    pub fn with_color_named(self, name: &str) -> crate::Result<Self> {
+        let color = color::named(name).ok_or_else(|| Error::NameNotFound(name.to_owned()))?;
+        Ok(Self {
+            color_temperature: Some(Adjust::Override(color.color_temperature)),
+            color_space_coordinates: Some(Adjust::Override(color.space_coordinates)),
+            ..self
+        })
+    }
+
+    /// Removes all attributes that are not supported by the given light.
+    ///
+    /// Sending an attribute that a light does not support results in an error response, so this
+    /// can be used before sending the same modifier to lights of different kinds. See
+    /// [`Light::supports_color`], [`Light::supports_color_temperature`] and
+    /// [`Light::supports_dimming`] for how the supported attributes are determined.
+    pub fn for_light(self, light: &Light) -> Self {
+        let mut modifier = self;
+        if !light.supports_dimming() {
+            modifier.brightness = None;
+        }
+        if !light.supports_color() {
+            modifier.hue = None;
+            modifier.saturation = None;
+            modifier.color_space_coordinates = None;
+            modifier.effect = None;
+        }
+        if !light.supports_color_temperature() {
+            modifier.color_temperature = None;
+        }
+        modifier
+    }
+
+    /// Returns whether a light state has the values that are set by this modifier.
+    ///
+    /// Only attributes that are overridden are compared, increments, decrements, the alert effect
+    /// and the transition time are ignored. Integer values may differ by one and color space
+    /// coordinates by 0.001, because the bridge rounds values while converting between color
+    /// modes. Values that are out of range of the light, for example a color outside of its gamut,
+    /// never match.
+    pub fn matches(&self, state: &State) -> bool {
+        fn matches_int<T: Into<i32>>(expected: Option<Adjust<T>>, actual: Option<T>) -> bool {
+            match (expected, actual) {
+                (Some(Adjust::Override(expected)), Some(actual)) => {
+                    (expected.into() - actual.into()).abs() <= 1
+                }
+                (Some(Adjust::Override(_)), None) => false,
+                _ => true,
+            }
+        }
+        let color_space_coordinates =
+            match (self.color_space_coordinates, state.color_space_coordinates) {
+                (Some(Adjust::Override((x, y))), Some((actual_x, actual_y))) => {
+                    (x - actual_x).abs() <= 0.001 && (y - actual_y).abs() <= 0.001
+                }
+                (Some(Adjust::Override(_)), None) => false,
+                _ => true,
+            };
+        (self.on.is_none() || state.on == self.on)
+            && (self.effect.is_none() || state.effect == self.effect)
+            && matches_int(self.brightness, state.brightness)
+            && matches_int(self.hue, state.hue)
+            && matches_int(self.saturation, state.saturation)
+            && matches_int(self.color_temperature, state.color_temperature)
+            && color_space_coordinates
+    }
 }
 
 impl resource::Modifier for StateModifier {
@@ -366,7 +1108,98 @@ impl Serialize for StateModifier {
     }
 }
 
+impl<'de> Deserialize<'de> for StateModifier {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Fields {
+            on: Option<bool>,
+            bri: Option<u8>,
+            bri_inc: Option<i32>,
+            hue: Option<u16>,
+            hue_inc: Option<i32>,
+            sat: Option<u8>,
+            sat_inc: Option<i32>,
+            xy: Option<(f32, f32)>,
+            xy_inc: Option<(f32, f32)>,
+            ct: Option<u16>,
+            ct_inc: Option<i32>,
+            alert: Option<Alert>,
+            effect: Option<Effect>,
+            transitiontime: Option<u16>,
+        }
+
+        fn adjust<T, E>(
+            name: &str,
+            value: Option<T>,
+            inc: Option<i32>,
+        ) -> Result<Option<Adjust<T>>, E>
+        where
+            T: TryFrom<u32>,
+            E: de::Error,
+        {
+            match (value, inc) {
+                (Some(_), Some(_)) => Err(E::custom(format_args!(
+                    "attributes `{0}` and `{0}_inc` are mutually exclusive",
+                    name
+                ))),
+                (Some(v), None) => Ok(Some(Adjust::Override(v))),
+                (None, Some(v)) => {
+                    let abs = T::try_from(v.unsigned_abs()).map_err(|_| {
+                        E::custom(format_args!(
+                            "value {} of `{}_inc` is out of range",
+                            v, name
+                        ))
+                    })?;
+                    Ok(Some(if v < 0 {
+                        Adjust::Decrement(abs)
+                    } else {
+                        Adjust::Increment(abs)
+                    }))
+                }
+                (None, None) => Ok(None),
+            }
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+        let color_space_coordinates = match (fields.xy, fields.xy_inc) {
+            (Some(_), Some(_)) => {
+                return Err(de::Error::custom(
+                    "attributes `xy` and `xy_inc` are mutually exclusive",
+                ))
+            }
+            (Some(v), None) => Some(Adjust::Override(v)),
+            (None, v) => v.map(Adjust::Increment),
+        };
+        Ok(Self {
+            on: fields.on,
+            brightness: adjust("bri", fields.bri, fields.bri_inc)?,
+            hue: adjust("hue", fields.hue, fields.hue_inc)?,
+            saturation: adjust("sat", fields.sat, fields.sat_inc)?,
+            color_space_coordinates,
+            color_temperature: adjust("ct", fields.ct, fields.ct_inc)?,
+            alert: fields.alert,
+            effect: fields.effect,
+            transition_time: fields.transitiontime,
+        })
+    }
+}
+
+impl TryFrom<JsonValue> for StateModifier {
+    type Error = Error;
+
+    fn try_from(value: JsonValue) -> crate::Result<Self> {
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
 /// Scanner for new lights.
+///
+/// If no device identifiers are set, the body of the request is an empty object and the bridge
+/// searches for all new lights.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Scanner {
@@ -393,6 +1226,96 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn deserialize_kind() {
+        let value: Kind = serde_json::from_value(json!("Extended color light")).unwrap();
+        assert_eq!(value, Kind::ExtendedColorLight);
+        let value: Kind = serde_json::from_value(json!("Dimmable light")).unwrap();
+        assert_eq!(value, Kind::DimmableLight);
+        let value: Kind = serde_json::from_value(json!("On/Off plug-in unit")).unwrap();
+        assert_eq!(value, Kind::OnOffPlugInUnit);
+        let value: Kind = serde_json::from_value(json!("Something else")).unwrap();
+        assert_eq!(value, Kind::Other("Something else".into()));
+    }
+
+    #[test]
+    fn deserialize_state() {
+        let json = json!({"on": true, "reachable": false});
+        let state: State = serde_json::from_value(json).unwrap();
+        assert_eq!(state.on, Some(true));
+        assert!(!state.reachable);
+
+        let json = json!({"on": false});
+        let state: State = serde_json::from_value(json).unwrap();
+        assert!(state.reachable);
+    }
+
+    #[test]
+    fn deserialize_control_capabilities() {
+        let json = json!({
+            "colorgamut": [[0.6915, 0.3083], [0.17, 0.7], [0.1532, 0.0475]],
+            "colorgamuttype": "C"
+        });
+        let value: ControlCapabilities = serde_json::from_value(json).unwrap();
+        assert_eq!(value.color_gamut_type, Some(ColorGamutType::C));
+
+        let json = json!({"colorgamuttype": "other"});
+        let value: ControlCapabilities = serde_json::from_value(json).unwrap();
+        assert_eq!(value.color_gamut_type, Some(ColorGamutType::Other));
+    }
+
+    #[test]
+    fn color_gamut() {
+        let gamut = ColorGamut {
+            kind: ColorGamutType::C,
+            red: (0.6915, 0.3083),
+            green: (0.17, 0.7),
+            blue: (0.1532, 0.0475),
+        };
+        assert!(gamut.contains((0.3, 0.3)));
+        assert_eq!(gamut.clamp((0.3, 0.3)), (0.3, 0.3));
+        assert!(!gamut.contains((0.0, 0.0)));
+        let clamped = gamut.clamp((0.0, 0.0));
+        assert!((clamped.0 - 0.1532).abs() < 0.01 && (clamped.1 - 0.0475).abs() < 0.01);
+        let clamped = gamut.clamp((0.8, 0.3));
+        assert!((clamped.0 - 0.6915).abs() < 0.01 && (clamped.1 - 0.3083).abs() < 0.01);
+    }
+
+    #[test]
+    fn color_gamut_palette() {
+        let gamut = ColorGamut {
+            kind: ColorGamutType::C,
+            red: (0.6915, 0.3083),
+            green: (0.17, 0.7),
+            blue: (0.1532, 0.0475),
+        };
+        let in_gamut = |v: (f32, f32)| {
+            let clamped = gamut.clamp(v);
+            (clamped.0 - v.0).abs() < 1e-4 && (clamped.1 - v.1).abs() < 1e-4
+        };
+        let red = gamut.red;
+        let complementary = gamut.complementary(red);
+        assert!(in_gamut(complementary));
+        assert!(complementary.0 < 0.3);
+        let colors = gamut.analogous(red, 3, 30.0);
+        assert_eq!(colors.len(), 3);
+        assert!(colors.iter().all(|&v| in_gamut(v)));
+        assert_eq!(colors[1], gamut.clamp(color::rotate_hue(red.0, red.1, 0.0)));
+        assert_eq!(colors, gamut.analogous(red, 3, 30.0));
+        assert!(gamut.analogous(red, 0, 30.0).is_empty());
+    }
+
+    #[cfg(feature = "lenient")]
+    #[test]
+    fn deserialize_lenient_state() {
+        let json = json!({"on": "true", "bri": "254", "ct": 366, "reachable": "false"});
+        let state: State = serde_json::from_value(json).unwrap();
+        assert_eq!(state.on, Some(true));
+        assert_eq!(state.brightness, Some(254));
+        assert_eq!(state.color_temperature, Some(366));
+        assert!(!state.reachable);
+    }
+
     #[test]
     fn serialize_attribute_modifier() {
         let modifier = AttributeModifier::new();
@@ -448,6 +1371,37 @@ mod tests {
         assert_eq!(modifier_json, expected_json);
     }
 
+    #[test]
+    fn state_modifier_with_color_named() {
+        let modifier = StateModifier::new().with_color_named("relax").unwrap();
+        assert_eq!(modifier.color_temperature, Some(Adjust::Override(447)));
+        assert_eq!(
+            modifier.color_space_coordinates,
+            Some(Adjust::Override((0.5019, 0.4152)))
+        );
+
+        assert!(matches!(
+            StateModifier::new().with_color_named("sunset"),
+            Err(Error::NameNotFound(name)) if name == "sunset"
+        ));
+    }
+
+    #[test]
+    fn state_modifier_ensure_on() {
+        let modifier = StateModifier::new().with_brightness_on(100);
+        assert_eq!(modifier.on, Some(true));
+        assert_eq!(modifier.brightness, Some(Adjust::Override(100)));
+
+        let modifier = StateModifier::new().with_hue(Adjust::Increment(10));
+        assert_eq!(modifier.ensure_on().on, Some(true));
+        let modifier = StateModifier::new()
+            .with_on(false)
+            .with_brightness(Adjust::Override(10));
+        assert_eq!(modifier.ensure_on().on, Some(false));
+        let modifier = StateModifier::new().with_alert(Alert::Select);
+        assert_eq!(modifier.ensure_on().on, None);
+    }
+
     #[test]
     fn serialize_state_modifier() {
         let modifier = StateModifier::new();
@@ -488,6 +1442,447 @@ mod tests {
             "xy": [0.0, 0.0]
         });
         assert_eq!(modifier_json, expected_json);
+
+        let modifier = StateModifier::new()
+            .with_brightness(Adjust::Decrement(10))
+            .with_hue(Adjust::Increment(1000))
+            .with_saturation(Adjust::Increment(20))
+            .with_color_space_coordinates(Adjust::Decrement((0.5, 0.25)))
+            .with_color_temperature(Adjust::Decrement(50));
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({
+            "bri_inc": -10,
+            "hue_inc": 1000,
+            "sat_inc": 20,
+            "xy_inc": [-0.5, -0.25],
+            "ct_inc": -50
+        });
+        assert_eq!(modifier_json, expected_json);
+
+        let cases = [
+            (CoordinateModifierType::Override, json!({"xy": [0.5, 0.25]})),
+            (
+                CoordinateModifierType::Increment,
+                json!({"xy_inc": [0.5, 0.25]}),
+            ),
+            (
+                CoordinateModifierType::Decrement,
+                json!({"xy_inc": [-0.5, -0.25]}),
+            ),
+            (
+                CoordinateModifierType::IncrementDecrement,
+                json!({"xy_inc": [0.5, -0.25]}),
+            ),
+            (
+                CoordinateModifierType::DecrementIncrement,
+                json!({"xy_inc": [-0.5, 0.25]}),
+            ),
+        ];
+        for (kind, expected_json) in cases.iter() {
+            let modifier =
+                StateModifier::new().with_color_space_coordinates_modify(*kind, (0.5, 0.25));
+            let modifier_json = serde_json::to_value(modifier).unwrap();
+            assert_eq!(&modifier_json, expected_json);
+        }
+    }
+
+    #[test]
+    fn deserialize_state_modifier() {
+        let modifier = StateModifier::new()
+            .with_on(true)
+            .with_brightness(Adjust::Decrement(10))
+            .with_hue(Adjust::Increment(1000))
+            .with_saturation(Adjust::Override(200))
+            .with_color_space_coordinates(Adjust::Increment((0.1, -0.1)))
+            .with_color_temperature(Adjust::Override(300))
+            .with_alert(Alert::Select)
+            .with_effect(Effect::Colorloop)
+            .with_transition_time(4);
+        let json = serde_json::to_value(&modifier).unwrap();
+        assert_eq!(StateModifier::try_from(json).unwrap(), modifier);
+
+        let modifier = StateModifier::new()
+            .with_color_space_coordinates(Adjust::Decrement((0.125, 0.25)))
+            .with_hue(Adjust::Decrement(1000));
+        let json = serde_json::to_value(&modifier).unwrap();
+        let restored = StateModifier::try_from(json.clone()).unwrap();
+        assert_eq!(
+            restored,
+            modifier.with_color_space_coordinates(Adjust::Increment((-0.125, -0.25)))
+        );
+        assert_eq!(serde_json::to_value(&restored).unwrap(), json);
+
+        assert_eq!(
+            StateModifier::try_from(json!({})).unwrap(),
+            StateModifier::new()
+        );
+        assert!(StateModifier::try_from(json!({"bri": 10, "bri_inc": 10})).is_err());
+        assert!(StateModifier::try_from(json!({"bri_inc": 1000})).is_err());
+        assert!(StateModifier::try_from(json!({"brightness": 10})).is_err());
+        assert!(StateModifier::try_from(json!({"on": "yes"})).is_err());
+    }
+
+    #[test]
+    fn diff() {
+        let json = json!({
+            "name": "test",
+            "type": "Extended color light",
+            "state": {"on": true, "bri": 100, "xy": [0.3, 0.3], "ct": 300, "reachable": true},
+            "modelid": "LCT001",
+            "uniqueid": "00:00:00:00:00:00:00:00-0b",
+            "swversion": "1.0"
+        });
+        let old: Light = serde_json::from_value(json).unwrap();
+        assert!(old.diff(&old).is_empty());
+
+        let mut new = old.clone();
+        new.state.on = Some(false);
+        new.state.brightness = None;
+        new.state.color_space_coordinates = Some((0.4, 0.3));
+        new.state.reachable = false;
+        let diff = old.diff(&new);
+        assert_eq!(diff.on, Some((Some(true), Some(false))));
+        assert_eq!(diff.brightness, Some((Some(100), None)));
+        assert_eq!(
+            diff.color_space_coordinates,
+            Some((Some((0.3, 0.3)), Some((0.4, 0.3))))
+        );
+        assert_eq!(diff.reachable, Some((true, false)));
+        assert_eq!(diff.color_temperature, None);
+        assert!(!diff.is_empty());
+    }
+
+    #[test]
+    fn deserialize_light() {
+        let json = json!({
+            "name": "test",
+            "type": "Extended color light",
+            "state": {"on": true, "bri": 100, "xy": [0.3, 0.3], "ct": 300, "reachable": true},
+            "modelid": "LCT001",
+            "uniqueid": "00:00:00:00:00:00:00:00-0b",
+            "swversion": "66009461"
+        });
+        let light: Light = serde_json::from_value(json).unwrap();
+        assert_eq!(light.kind, Kind::ExtendedColorLight);
+        #[cfg(not(feature = "old-api"))]
+        {
+            assert_eq!(light.software_update, None);
+            assert_eq!(light.config, None);
+            assert_eq!(light.capabilities, None);
+            assert_eq!(light.color_gamut(), None);
+        }
+    }
+
+    #[test]
+    fn deserialize_software_update() {
+        let json = json!({"state": "readytoinstall", "lastinstall": "2020-01-01T00:00:00"});
+        let software_update: SoftwareUpdate = serde_json::from_value(json).unwrap();
+        assert_eq!(software_update.state, SoftwareUpdateState::ReadyToInstall);
+        assert!(software_update.last_install.is_some());
+
+        let json = json!({"state": "somethingnew", "lastinstall": null});
+        let software_update: SoftwareUpdate = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            software_update.state,
+            SoftwareUpdateState::Other("somethingnew".into())
+        );
+        assert_eq!(software_update.last_install, None);
+    }
+
+    #[test]
+    fn deserialize_startup_config() {
+        let json = json!({"mode": "powerfail", "configured": true});
+        let startup: StartupConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(startup.mode, StartupMode::PowerFail);
+        assert_eq!(startup.custom_settings, None);
+
+        let json = json!({
+            "mode": "custom",
+            "configured": true,
+            "customsettings": {"bri": 254, "ct": 366}
+        });
+        let startup: StartupConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(startup.mode, StartupMode::Custom);
+        assert_eq!(
+            startup.custom_settings,
+            Some(
+                StartupSettings::new()
+                    .with_brightness(254)
+                    .with_color_temperature(366)
+            )
+        );
+
+        let json = json!({"mode": "unknown", "configured": false});
+        let startup: StartupConfig = serde_json::from_value(json).unwrap();
+        assert_eq!(startup.mode, StartupMode::Other("unknown".into()));
+    }
+
+    #[test]
+    fn serialize_config_modifier() {
+        let modifier = ConfigModifier::new();
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({});
+        assert_eq!(modifier_json, expected_json);
+
+        let modifier =
+            ConfigModifier::new().with_startup(StartupModifier::new(StartupMode::LastOnState));
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({"startup": {"mode": "lastonstate"}});
+        assert_eq!(modifier_json, expected_json);
+
+        let modifier = ConfigModifier::new().with_startup(
+            StartupModifier::new(StartupMode::Custom)
+                .with_custom_settings(StartupSettings::new().with_brightness(100)),
+        );
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({
+            "startup": {"mode": "custom", "customsettings": {"bri": 100}}
+        });
+        assert_eq!(modifier_json, expected_json);
+
+        let modifier = ConfigModifier::new()
+            .with_arche_type("pendantround".into())
+            .with_function("decorative".into())
+            .with_direction("downwards".into());
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({
+            "archetype": "pendantround",
+            "function": "decorative",
+            "direction": "downwards"
+        });
+        assert_eq!(modifier_json, expected_json);
+    }
+
+    #[test]
+    fn current_color() {
+        let json = json!({
+            "name": "test",
+            "type": "Extended color light",
+            "state": {
+                "on": true,
+                "bri": 100,
+                "hue": 7688,
+                "sat": 199,
+                "xy": [0.5, 0.4],
+                "ct": 443,
+                "colormode": "ct",
+                "reachable": true
+            },
+            "modelid": "LCT015",
+            "uniqueid": "00:00:00:00:00:00:00:00-0b",
+            "swversion": "1.0"
+        });
+        let light: Light = serde_json::from_value(json).unwrap();
+        assert_eq!(light.current_xy(), Some((0.5, 0.4)));
+        assert_eq!(light.current_hue_sat(), Some((7688, 199)));
+        assert_eq!(light.current_ct(), Some(443));
+        assert_eq!(
+            light.current_color_mode(),
+            Some(ColorMode::ColorTemperature)
+        );
+        assert_eq!(
+            light.current_rgb(),
+            Some(color::color_temperature_to_rgb(443, 100))
+        );
+
+        let light = Light {
+            kind: Kind::DimmableLight,
+            ..light
+        };
+        assert_eq!(light.current_xy(), None);
+        assert_eq!(light.current_hue_sat(), None);
+        assert_eq!(light.current_ct(), None);
+        assert_eq!(light.current_color_mode(), None);
+        assert_eq!(light.current_rgb(), None);
+    }
+
+    #[test]
+    fn snapshot() {
+        let json = json!({
+            "name": "test",
+            "type": "Extended color light",
+            "state": {
+                "on": true,
+                "bri": 100,
+                "hue": 7688,
+                "sat": 199,
+                "xy": [0.5, 0.4],
+                "ct": 443,
+                "effect": "none",
+                "colormode": "hs",
+                "reachable": false
+            },
+            "modelid": "LCT015",
+            "uniqueid": "00:00:00:00:00:00:00:00-0b",
+            "swversion": "1.0"
+        });
+        let light: Light = serde_json::from_value(json).unwrap();
+        let modifier = light.snapshot();
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({
+            "on": true,
+            "bri": 100,
+            "hue": 7688,
+            "sat": 199,
+            "effect": "none"
+        });
+        assert_eq!(modifier_json, expected_json);
+
+        let light = Light {
+            kind: Kind::DimmableLight,
+            ..light
+        };
+        let modifier_json = serde_json::to_value(light.snapshot()).unwrap();
+        assert_eq!(modifier_json, json!({"on": true, "bri": 100}));
+    }
+
+    #[test]
+    fn clamp_color_temperature() {
+        let json = json!({
+            "name": "test",
+            "type": "Color temperature light",
+            "state": {"on": true, "bri": 100, "ct": 300, "reachable": true},
+            "modelid": "LTW001",
+            "uniqueid": "00:00:00:00:00:00:00:00-0b",
+            "swversion": "1.0",
+            "capabilities": {
+                "certified": true,
+                "control": {"ct": {"min": 153, "max": 454}},
+                "streaming": {"renderer": false, "proxy": false}
+            }
+        });
+        let light: Light = serde_json::from_value(json).unwrap();
+        #[cfg(not(feature = "old-api"))]
+        assert_eq!(light.color_temperature_range(), 153..=454);
+        assert_eq!(light.clamp_color_temperature(100), 153);
+        assert_eq!(light.clamp_color_temperature(300), 300);
+        #[cfg(not(feature = "old-api"))]
+        assert_eq!(light.clamp_color_temperature(500), 454);
+
+        #[cfg(not(feature = "old-api"))]
+        {
+            let light = Light {
+                capabilities: None,
+                ..light
+            };
+            assert_eq!(light.color_temperature_range(), 153..=500);
+        }
+    }
+
+    #[test]
+    fn brightness_from_percent() {
+        let json = json!({
+            "name": "test",
+            "type": "Dimmable light",
+            "state": {"on": true, "bri": 100, "reachable": true},
+            "modelid": "LWB010",
+            "uniqueid": "00:00:00:00:00:00:00:00-0b",
+            "swversion": "1.0",
+            "capabilities": {
+                "certified": true,
+                "control": {"mindimlevel": 5000, "maxlumen": 806},
+                "streaming": {"renderer": false, "proxy": false}
+            }
+        });
+        let light: Light = serde_json::from_value(json).unwrap();
+        #[cfg(not(feature = "old-api"))]
+        {
+            assert_eq!(light.min_brightness(), 13);
+            assert_eq!(light.brightness_from_percent(0), 13);
+            assert_eq!(light.brightness_from_percent(1), 13);
+            assert_eq!(light.brightness_from_percent(50), 132);
+            let light = Light {
+                capabilities: None,
+                ..light.clone()
+            };
+            assert_eq!(light.min_brightness(), 1);
+        }
+        #[cfg(feature = "old-api")]
+        assert_eq!(light.brightness_from_percent(50), 126);
+        assert_eq!(light.brightness_from_percent(100), 254);
+        assert_eq!(light.brightness_from_percent(200), 254);
+    }
+
+    #[test]
+    fn state_modifier_for_light() {
+        let mut json = json!({
+            "name": "test",
+            "type": "Dimmable light",
+            "state": {"on": true, "bri": 100, "reachable": true},
+            "modelid": "LWB010",
+            "uniqueid": "00:00:00:00:00:00:00:00-0b",
+            "swversion": "1.0",
+            "swupdate": {"state": "noupdates", "lastinstall": null},
+            "config": {
+                "archetype": "classicbulb",
+                "function": "functional",
+                "direction": "omnidirectional"
+            },
+            "capabilities": {
+                "certified": true,
+                "control": {"mindimlevel": 5000, "maxlumen": 806},
+                "streaming": {"renderer": false, "proxy": false}
+            }
+        });
+        let light: Light = serde_json::from_value(json.clone()).unwrap();
+        assert!(light.supports_dimming());
+        assert!(!light.supports_color());
+        assert!(!light.supports_color_temperature());
+        let modifier = StateModifier::new()
+            .with_on(true)
+            .with_brightness(Adjust::Override(10))
+            .with_hue(Adjust::Override(10))
+            .with_color_temperature(Adjust::Override(300))
+            .for_light(&light);
+        assert_eq!(
+            modifier,
+            StateModifier::new()
+                .with_on(true)
+                .with_brightness(Adjust::Override(10))
+        );
+
+        json["type"] = json!("On/Off plug-in unit");
+        let light: Light = serde_json::from_value(json).unwrap();
+        assert!(!light.supports_dimming());
+        let modifier = StateModifier::new()
+            .with_brightness(Adjust::Override(10))
+            .for_light(&light);
+        assert_eq!(modifier, StateModifier::new());
+    }
+
+    #[test]
+    fn state_modifier_matches() {
+        let state: State = serde_json::from_value(json!({
+            "on": true,
+            "bri": 100,
+            "hue": 1000,
+            "sat": 200,
+            "xy": [0.3127, 0.329],
+            "ct": 300,
+            "alert": "none",
+            "effect": "none",
+            "reachable": true
+        }))
+        .unwrap();
+        assert!(StateModifier::new().matches(&state));
+        let modifier = StateModifier::new()
+            .with_on(true)
+            .with_brightness(Adjust::Override(101))
+            .with_color_space_coordinates(Adjust::Override((0.3125, 0.3291)))
+            .with_color_temperature(Adjust::Increment(100))
+            .with_alert(Alert::Select)
+            .with_transition_time(10);
+        assert!(modifier.matches(&state));
+        assert!(!modifier.clone().with_on(false).matches(&state));
+        assert!(!modifier
+            .clone()
+            .with_brightness(Adjust::Override(102))
+            .matches(&state));
+        assert!(!modifier
+            .clone()
+            .with_color_space_coordinates(Adjust::Override((0.32, 0.329)))
+            .matches(&state));
+        assert!(!modifier.with_effect(Effect::Colorloop).matches(&state));
     }
 
     #[test]
@@ -36,7 +36,7 @@ pub mod schedule;
 pub mod sensor;
 
 pub use capabilities::Capabilities;
-pub use config::Config;
+pub use config::{Config, PublicConfig};
 pub use group::Group;
 pub use light::Light;
 pub use resourcelink::Resourcelink;
@@ -45,7 +45,7 @@ pub use scene::Scene;
 pub use schedule::Schedule;
 pub use sensor::Sensor;
 
-use crate::{response::Modified, Bridge, Error, Response};
+use crate::{response::Modified, util, Bridge, Error, Response};
 use chrono::NaiveDateTime;
 use serde::{de, de::Error as _, Deserialize, Serialize};
 use serde_json::Value as JsonValue;
@@ -175,9 +175,7 @@ impl<'de> Deserialize<'de> for LastScan {
         Ok(match value.as_ref() {
             "active" => LastScan::Active,
             "none" => LastScan::None,
-            v => LastScan::DateTime(
-                NaiveDateTime::parse_from_str(v, "%Y-%m-%dT%H:%M:%S").map_err(D::Error::custom)?,
-            ),
+            v => LastScan::DateTime(util::parse_date_time(v).map_err(D::Error::custom)?),
         })
     }
 }
@@ -192,6 +190,9 @@ pub struct ScanResource {
 }
 
 /// Enum for adjusting an attribute of a modifier or creator.
+///
+/// Increments and decrements are sent to the bridge using the `*_inc` attributes (e.g. `bri_inc`
+/// or `xy_inc`), where a decrement is sent as a negative increment.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub enum Adjust<T> {
     /// Overrides the current value.
@@ -202,6 +203,37 @@ pub enum Adjust<T> {
     Decrement(T),
 }
 
+/// Kind of adjustment of color space coordinates.
+///
+/// In comparison to [`Adjust`], the x and y coordinates can be adjusted in different directions.
+/// See [`light::StateModifier::with_color_space_coordinates_modify`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum CoordinateModifierType {
+    /// Overrides the current coordinates.
+    Override,
+    /// Adds both values to the current coordinates.
+    Increment,
+    /// Subtracts both values from the current coordinates.
+    Decrement,
+    /// Adds the x value to and subtracts the y value from the current coordinates.
+    IncrementDecrement,
+    /// Subtracts the x value from and adds the y value to the current coordinates.
+    DecrementIncrement,
+}
+
+impl CoordinateModifierType {
+    /// Converts the coordinates into an [`Adjust`] with signed components.
+    pub fn adjust(self, (x, y): (f32, f32)) -> Adjust<(f32, f32)> {
+        match self {
+            Self::Override => Adjust::Override((x, y)),
+            Self::Increment => Adjust::Increment((x, y)),
+            Self::Decrement => Adjust::Decrement((x, y)),
+            Self::IncrementDecrement => Adjust::Increment((x, -y)),
+            Self::DecrementIncrement => Adjust::Increment((-x, y)),
+        }
+    }
+}
+
 /// Represents a HTTP method.
 #[allow(missing_docs)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -216,7 +248,37 @@ pub enum RequestMethod {
 pub trait Resource {}
 
 /// Trait for creating a resource.
+///
+/// Every creator is constructed with a `new` function that takes the attributes that are
+/// required by the bridge, which is also available as [`from_required`] for generic code.
+/// Optional attributes are set with the `with_*` methods, which can be chained.
+///
+/// # Examples
+///
+/// ```
+/// use huelib::resource::{group, Creator};
+///
+/// let creator = group::Creator::new("Office".into(), vec!["1".into(), "2".into()])
+///     .with_kind(group::CreatableKind::Room)
+///     .with_class(group::Class::Office);
+/// assert_eq!(group::Creator::url_suffix(), "groups");
+///
+/// let required = ("Office".into(), vec!["1".into(), "2".into()]);
+/// assert_eq!(
+///     group::Creator::from_required(required),
+///     group::Creator::new("Office".into(), vec!["1".into(), "2".into()])
+/// );
+/// ```
+///
+/// [`from_required`]: Self::from_required
 pub trait Creator: Serialize {
+    /// Attributes that are required to create the resource, in the order of the arguments of the
+    /// `new` function of the creator.
+    type Required;
+
+    /// Creates a creator from the attributes that are required to create the resource.
+    fn from_required(required: Self::Required) -> Self;
+
     /// Returns the suffix of the API URL.
     fn url_suffix() -> String;
 
@@ -236,6 +298,13 @@ pub trait Creator: Serialize {
             None => Err(Error::GetCreatedId),
         }
     }
+
+    /// Returns the JSON body that is sent to the bridge, formatted for inspection.
+    ///
+    /// See [`Modifier::debug_json`].
+    fn debug_json(&self) -> String {
+        debug_json(self)
+    }
 }
 
 /// Trait for modifying a resource.
@@ -256,6 +325,23 @@ pub trait Modifier: Serialize {
             Some(serde_json::to_value(self)?),
         )
     }
+
+    /// Returns the JSON body that is sent to the bridge, formatted for inspection.
+    ///
+    /// This is useful to find out why the bridge rejects a modifier. If the modifier cannot be
+    /// serialized, the error message is returned instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use huelib::resource::{light, Modifier};
+    ///
+    /// let modifier = light::StateModifier::new().with_on(true);
+    /// assert_eq!(modifier.debug_json(), "{\n  \"on\": true\n}");
+    /// ```
+    fn debug_json(&self) -> String {
+        debug_json(self)
+    }
 }
 
 /// Trait for scanning new resources.
@@ -264,6 +350,9 @@ pub trait Scanner: Serialize {
     fn url_suffix() -> String;
 
     /// Sends the request to scan for new resources.
+    ///
+    /// The scanner is sent as JSON object in the body of the request, so a scanner without any
+    /// attributes is sent as `{}`.
     fn execute(&self, bridge: &Bridge) -> crate::Result<()> {
         let responses: Vec<Response<JsonValue>> = bridge.api_request(
             Self::url_suffix(),
@@ -275,6 +364,18 @@ pub trait Scanner: Serialize {
         }
         Ok(())
     }
+
+    /// Returns the JSON body that is sent to the bridge, formatted for inspection.
+    ///
+    /// See [`Modifier::debug_json`].
+    fn debug_json(&self) -> String {
+        debug_json(self)
+    }
+}
+
+fn debug_json<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string_pretty(value)
+        .unwrap_or_else(|e| format!("failed to serialize JSON: {}", e))
 }
 
 #[cfg(test)]
@@ -283,6 +384,31 @@ mod tests {
     use chrono::{NaiveDate, NaiveTime};
     use serde_json::json;
 
+    #[test]
+    fn coordinate_modifier_type() {
+        let value = (0.1, 0.2);
+        assert_eq!(
+            CoordinateModifierType::Override.adjust(value),
+            Adjust::Override((0.1, 0.2))
+        );
+        assert_eq!(
+            CoordinateModifierType::Increment.adjust(value),
+            Adjust::Increment((0.1, 0.2))
+        );
+        assert_eq!(
+            CoordinateModifierType::Decrement.adjust(value),
+            Adjust::Decrement((0.1, 0.2))
+        );
+        assert_eq!(
+            CoordinateModifierType::IncrementDecrement.adjust(value),
+            Adjust::Increment((0.1, -0.2))
+        );
+        assert_eq!(
+            CoordinateModifierType::DecrementIncrement.adjust(value),
+            Adjust::Increment((-0.1, 0.2))
+        );
+    }
+
     #[test]
     fn deserialize_last_scan() {
         let json = json!("none");
@@ -295,9 +421,15 @@ mod tests {
 
         let json = json!("2020-01-01T00:10:00");
         let value: LastScan = serde_json::from_value(json).unwrap();
-        let date = NaiveDate::from_ymd(2020, 1, 1);
-        let time = NaiveTime::from_hms(0, 10, 0);
-        assert_eq!(value, LastScan::DateTime(NaiveDateTime::new(date, time)))
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let time = NaiveTime::from_hms_opt(0, 10, 0).unwrap();
+        assert_eq!(value, LastScan::DateTime(NaiveDateTime::new(date, time)));
+
+        let json = json!("2020-01-02T03:04:05Z");
+        let value: LastScan = serde_json::from_value(json).unwrap();
+        let date = NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+        let time = NaiveTime::from_hms_opt(3, 4, 5).unwrap();
+        assert_eq!(value, LastScan::DateTime(NaiveDateTime::new(date, time)));
     }
 
     #[test]
@@ -1,6 +1,7 @@
 use crate::resource;
 use derive_setters::Setters;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
 
 /// A resourcelink to group resources in the bridge.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
@@ -50,6 +51,25 @@ pub struct Link {
     pub id: String,
 }
 
+impl Link {
+    /// Creates a new [`Link`].
+    pub fn new<S>(kind: LinkKind, id: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            kind,
+            id: id.into(),
+        }
+    }
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}/{}", self.kind.as_str(), self.id)
+    }
+}
+
 impl<'de> Deserialize<'de> for Link {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         let value: String = Deserialize::deserialize(deserializer)?;
@@ -73,7 +93,7 @@ impl Serialize for Link {
     where
         S: Serializer,
     {
-        serializer.serialize_str(&format!("/{}/{}", self.kind.as_str(), self.id))
+        serializer.serialize_str(&self.to_string())
     }
 }
 
@@ -161,6 +181,12 @@ impl Creator {
 }
 
 impl resource::Creator for Creator {
+    type Required = (String, u16, Vec<Link>);
+
+    fn from_required((name, class_id, links): Self::Required) -> Self {
+        Self::new(name, class_id, links)
+    }
+
     fn url_suffix() -> String {
         "resourcelinks".to_owned()
     }
@@ -176,10 +202,10 @@ pub struct Modifier {
     /// Sets the description of the resourcelink.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    /// Sets the class id of the resourcelink.
+    /// Sets the kind of the resourcelink.
     #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
     pub kind: Option<Kind>,
-    /// Sets the kind of the resourcelink.
+    /// Sets the class id of the resourcelink.
     #[serde(skip_serializing_if = "Option::is_none", rename = "classid")]
     pub class_id: Option<u16>,
     /// Sets the links of the resourcelink.
@@ -206,6 +232,40 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn deserialize_resourcelink() {
+        let json = json!({
+            "name": "test",
+            "description": "description test",
+            "type": "Link",
+            "classid": 10010,
+            "owner": "user",
+            "recycle": false,
+            "links": ["/lights/1", "/groups/3", "/sensors/5"]
+        });
+        let resourcelink: Resourcelink = serde_json::from_value(json).unwrap();
+        assert_eq!(resourcelink.class_id, 10010);
+        assert_eq!(
+            resourcelink.links,
+            vec![
+                Link::new(LinkKind::Light, "1"),
+                Link::new(LinkKind::Group, "3"),
+                Link::new(LinkKind::Sensor, "5"),
+            ]
+        );
+
+        let json = json!({
+            "name": "test",
+            "description": "",
+            "type": "Link",
+            "classid": 1,
+            "owner": "user",
+            "recycle": false,
+            "links": ["/unknown/1"]
+        });
+        assert!(serde_json::from_value::<Resourcelink>(json).is_err());
+    }
+
     #[test]
     fn serialize_creator() {
         let links = vec![
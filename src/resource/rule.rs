@@ -13,18 +13,20 @@ pub struct Rule {
     /// Name of the rule.
     pub name: String,
     /// Owner of the rule.
-    #[serde(deserialize_with = "util::deserialize_option_string")]
+    #[serde(default, deserialize_with = "util::deserialize_option_string")]
     pub owner: Option<String>,
     /// When the rule was last triggered.
     #[serde(
         rename = "lasttriggered",
+        default,
         deserialize_with = "util::deserialize_option_date_time"
     )]
     pub last_triggered: Option<NaiveDateTime>,
     /// How often the rule was triggered.
-    #[serde(rename = "timestriggered")]
+    #[serde(rename = "timestriggered", default)]
     pub times_triggered: usize,
     /// When the rule was created.
+    #[serde(deserialize_with = "util::deserialize_date_time")]
     pub created: NaiveDateTime,
     /// Status of the rule.
     pub status: Status,
@@ -184,6 +186,9 @@ pub struct Creator {
     /// Sets the actions of the rule.
     #[setters(skip)]
     pub actions: Vec<Action>,
+    /// Sets whether resource is automatically deleted when not referenced anymore.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recycle: Option<bool>,
 }
 
 impl Creator {
@@ -194,11 +199,18 @@ impl Creator {
             status: None,
             conditions,
             actions,
+            recycle: None,
         }
     }
 }
 
 impl resource::Creator for Creator {
+    type Required = (Vec<Condition>, Vec<Action>);
+
+    fn from_required((conditions, actions): Self::Required) -> Self {
+        Self::new(conditions, actions)
+    }
+
     fn url_suffix() -> String {
         "rules".to_owned()
     }
@@ -331,6 +343,7 @@ mod tests {
             status: Some(Status::Enabled),
             conditions,
             actions,
+            recycle: Some(true),
         };
         let creator_json = serde_json::to_value(creator).unwrap();
         let expected_json = json!({
@@ -349,6 +362,7 @@ mod tests {
                     "body": {}
                 }
             ],
+            "recycle": true
         });
         assert_eq!(creator_json, expected_json);
     }
@@ -4,9 +4,13 @@ use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 use serde_repr::Deserialize_repr;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Scene presets of the Philips Hue app.
+pub mod preset;
 
 /// A scene.
-#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize)]
 pub struct Scene {
     /// Identifier of the scene.
     #[serde(skip_deserializing)]
@@ -21,17 +25,19 @@ pub struct Scene {
     /// Identifier of the lights that are in this scene.
     pub lights: Option<Vec<String>>,
     /// Whitelist user that created or modified the content of the scene.
-    #[serde(deserialize_with = "util::deserialize_option_string")]
+    #[serde(default, deserialize_with = "util::deserialize_option_string")]
     pub owner: Option<String>,
     /// Whether the group is automatically deleted when not referenced anymore.
+    #[serde(default)]
     pub recycle: bool,
     /// Whether the scene is locked by a rule or a schedule.
     ///
     /// If set to true, the scene cannot be deleted until all resources requiring or that reference
     /// the scene are deleted.
+    #[serde(default)]
     pub locked: bool,
     /// App specific data linked to the scene.
-    #[serde(rename = "appdata")]
+    #[serde(rename = "appdata", default)]
     pub app_data: AppData,
     /// Only available with an individual scene resource.
     ///
@@ -40,10 +46,22 @@ pub struct Scene {
     /// Time the scene has been created or updated.
     ///
     /// Not available for legacy scenes.
-    #[serde(rename = "lastupdate")]
+    #[serde(
+        rename = "lastupdated",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
     pub last_update: Option<chrono::NaiveDateTime>,
     /// Version of the scene document.
     pub version: Version,
+    /// States of the lights in the scene.
+    ///
+    /// The keys of the HashMap are the light identifiers. Only available with an individual scene
+    /// resource (see [`Bridge::get_scene`]).
+    ///
+    /// [`Bridge::get_scene`]: crate::Bridge::get_scene
+    #[serde(rename = "lightstates")]
+    pub light_states: Option<HashMap<String, light::StaticStateModifier>>,
 }
 
 impl Scene {
@@ -52,6 +70,43 @@ impl Scene {
     }
 }
 
+// The color space coordinates of the light states are never NaN, because they can't be
+// represented in JSON.
+impl Eq for Scene {}
+
+impl Hash for Scene {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The light states are skipped, because they contain floats and are stored in a HashMap.
+        let Self {
+            id,
+            name,
+            kind,
+            group,
+            lights,
+            owner,
+            recycle,
+            locked,
+            app_data,
+            picture,
+            last_update,
+            version,
+            light_states: _,
+        } = self;
+        id.hash(state);
+        name.hash(state);
+        kind.hash(state);
+        group.hash(state);
+        lights.hash(state);
+        owner.hash(state);
+        recycle.hash(state);
+        locked.hash(state);
+        app_data.hash(state);
+        picture.hash(state);
+        last_update.hash(state);
+        version.hash(state);
+    }
+}
+
 impl resource::Resource for Scene {}
 
 /// Kind of a scene.
@@ -92,17 +147,29 @@ pub struct Creator {
     #[setters(skip)]
     pub name: String,
     /// Sets the light identifiers of the scene.
+    ///
+    /// Not sent if empty, because the lights of a [`Kind::GroupScene`] are the lights of its
+    /// group.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     #[setters(skip)]
     pub lights: Vec<String>,
     /// Sets the type of the scene.
     #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
     pub kind: Option<Kind>,
+    /// Sets the group of the scene.
+    ///
+    /// Only used if the type of the scene is [`Kind::GroupScene`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
     /// Sets the app data of the scene.
     #[serde(skip_serializing_if = "Option::is_none", rename = "appdata")]
     pub app_data: Option<AppData>,
     /// Sets the state of specific lights.
     #[serde(skip_serializing_if = "Option::is_none", rename = "lightstates")]
     pub light_states: Option<HashMap<String, light::StaticStateModifier>>,
+    /// Sets whether resource is automatically deleted when not referenced anymore.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recycle: Option<bool>,
 }
 
 impl Creator {
@@ -112,13 +179,44 @@ impl Creator {
             name,
             lights,
             kind: None,
+            group: None,
             app_data: None,
             light_states: None,
+            recycle: None,
+        }
+    }
+
+    /// Creates a new [`Creator`] that creates a copy of the given scene with another name.
+    ///
+    /// The type, group, app data and light states of the scene are copied. The light states are
+    /// only available if the scene was requested individually (see [`Scene::light_states`]).
+    pub fn from_scene(scene: &Scene, name: String) -> Self {
+        let lights = match scene.kind {
+            Kind::LightScene => scene.lights.clone().unwrap_or_default(),
+            Kind::GroupScene => Vec::new(),
+        };
+        Self {
+            name,
+            lights,
+            kind: Some(scene.kind),
+            group: match scene.kind {
+                Kind::LightScene => None,
+                Kind::GroupScene => scene.group.clone(),
+            },
+            app_data: Some(scene.app_data.clone()).filter(|v| *v != AppData::default()),
+            light_states: scene.light_states.clone(),
+            recycle: None,
         }
     }
 }
 
 impl resource::Creator for Creator {
+    type Required = (String, Vec<String>);
+
+    fn from_required((name, lights): Self::Required) -> Self {
+        Self::new(name, lights)
+    }
+
     fn url_suffix() -> String {
         "scenes".to_owned()
     }
@@ -163,6 +261,47 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn deserialize_scene() {
+        let json = json!({
+            "name": "test",
+            "type": "GroupScene",
+            "group": "1",
+            "lights": ["1", "2"],
+            "owner": "user",
+            "recycle": false,
+            "locked": false,
+            "appdata": {"version": 1, "data": "data test"},
+            "picture": "",
+            "lastupdated": "2020-01-01T00:00:00",
+            "version": 2
+        });
+        let scene: Scene = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            scene.app_data,
+            AppData {
+                version: Some(1),
+                data: Some("data test".into())
+            }
+        );
+        assert_eq!(scene.picture, Some("".into()));
+        assert!(scene.last_update.is_some());
+        assert_eq!(scene.version, Version::Post);
+
+        let json = json!({
+            "name": "test",
+            "type": "LightScene",
+            "owner": "none",
+            "recycle": true,
+            "locked": false,
+            "version": 1
+        });
+        let scene: Scene = serde_json::from_value(json).unwrap();
+        assert_eq!(scene.app_data, AppData::default());
+        assert_eq!(scene.picture, None);
+        assert_eq!(scene.last_update, None);
+    }
+
     #[test]
     fn serialize_creator() {
         let creator = Creator::new("test".into(), vec!["1".into()]);
@@ -177,22 +316,74 @@ mod tests {
             name: "test".into(),
             lights: vec!["1".into()],
             kind: Some(Kind::GroupScene),
+            group: Some("2".into()),
             app_data: Some(AppData {
                 version: Some(2),
                 data: Some("data test".into()),
             }),
             light_states: Some(HashMap::new()),
+            recycle: Some(true),
         };
         let creator_json = serde_json::to_value(creator).unwrap();
         let expected_json = json!({
             "name": "test",
             "lights": ["1"],
             "type": "GroupScene",
+            "group": "2",
             "appdata": {
                 "version": 2,
                 "data": "data test"
             },
-            "lightstates": {}
+            "lightstates": {},
+            "recycle": true
+        });
+        assert_eq!(creator_json, expected_json);
+    }
+
+    #[test]
+    fn creator_from_scene() {
+        let json = json!({
+            "name": "Relax",
+            "type": "GroupScene",
+            "group": "1",
+            "lights": ["1", "2"],
+            "owner": "user",
+            "recycle": false,
+            "locked": false,
+            "appdata": {},
+            "lastupdated": "2020-01-01T00:00:00",
+            "version": 2,
+            "lightstates": {
+                "1": {"on": true, "bri": 144, "ct": 447},
+                "2": {"on": false}
+            }
+        });
+        let scene: Scene = serde_json::from_value(json).unwrap();
+        let creator_json =
+            serde_json::to_value(Creator::from_scene(&scene, "Copy".into())).unwrap();
+        let expected_json = json!({
+            "name": "Copy",
+            "type": "GroupScene",
+            "group": "1",
+            "lightstates": {
+                "1": {"on": true, "bri": 144, "ct": 447},
+                "2": {"on": false}
+            }
+        });
+        assert_eq!(creator_json, expected_json);
+
+        let scene = Scene {
+            kind: Kind::LightScene,
+            group: None,
+            light_states: None,
+            ..scene
+        };
+        let creator_json =
+            serde_json::to_value(Creator::from_scene(&scene, "Copy".into())).unwrap();
+        let expected_json = json!({
+            "name": "Copy",
+            "lights": ["1", "2"],
+            "type": "LightScene"
         });
         assert_eq!(creator_json, expected_json);
     }
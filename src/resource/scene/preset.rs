@@ -0,0 +1,179 @@
+use crate::color::{self, NamedColor};
+use crate::resource::{light, scene::Creator, Light};
+use std::collections::HashMap;
+
+/// Light state of a preset.
+struct Preset {
+    name: &'static str,
+    brightness: u8,
+    color: NamedColor,
+}
+
+/// White tone of the `Bright` and `Dimmed` presets.
+const BRIGHT_WHITE: NamedColor = NamedColor {
+    name: "bright",
+    color_temperature: 366,
+    space_coordinates: (0.4573, 0.4100),
+};
+
+const BRIGHT: Preset = Preset {
+    name: "Bright",
+    brightness: 254,
+    color: BRIGHT_WHITE,
+};
+
+const DIMMED: Preset = Preset {
+    name: "Dimmed",
+    brightness: 77,
+    color: BRIGHT_WHITE,
+};
+
+const NIGHTLIGHT: Preset = Preset {
+    name: "Nightlight",
+    brightness: 1,
+    color: NamedColor {
+        name: "nightlight",
+        color_temperature: 500,
+        space_coordinates: (0.5612, 0.4042),
+    },
+};
+
+impl Preset {
+    /// Returns a preset that uses the named color with the same name.
+    fn named(name: &'static str, brightness: u8) -> Self {
+        let color = *color::named(name).expect("preset without a named color");
+        Self {
+            name,
+            brightness,
+            color,
+        }
+    }
+
+    fn creator(&self, lights: &[Light]) -> Creator {
+        let light_states: HashMap<String, light::StaticStateModifier> = lights
+            .iter()
+            .map(|light| (light.id.clone(), self.light_state(light)))
+            .collect();
+        Creator::new(
+            self.name.to_owned(),
+            lights.iter().map(|light| light.id.clone()).collect(),
+        )
+        .with_light_states(light_states)
+    }
+
+    fn light_state(&self, light: &Light) -> light::StaticStateModifier {
+        let mut state = light::StaticStateModifier::new().with_on(true);
+        if light.supports_dimming() {
+            state = state.with_brightness(self.brightness);
+        }
+        if light.supports_color_temperature() {
+            state = state.with_color_temperature(self.color.color_temperature);
+        } else if light.supports_color() {
+            #[cfg(not(feature = "old-api"))]
+            let space_coordinates = match light.color_gamut() {
+                Some(gamut) => gamut.clamp(self.color.space_coordinates),
+                None => self.color.space_coordinates,
+            };
+            #[cfg(feature = "old-api")]
+            let space_coordinates = self.color.space_coordinates;
+            state = state.with_color_space_coordinates(space_coordinates);
+        }
+        state
+    }
+}
+
+/// Returns a creator for the `Relax` scene with the given lights.
+pub fn relax(lights: &[Light]) -> Creator {
+    Preset::named("Relax", 144).creator(lights)
+}
+
+/// Returns a creator for the `Reading` scene with the given lights.
+pub fn reading(lights: &[Light]) -> Creator {
+    Preset::named("Reading", 254).creator(lights)
+}
+
+/// Returns a creator for the `Concentrate` scene with the given lights.
+pub fn concentrate(lights: &[Light]) -> Creator {
+    Preset::named("Concentrate", 254).creator(lights)
+}
+
+/// Returns a creator for the `Energize` scene with the given lights.
+pub fn energize(lights: &[Light]) -> Creator {
+    Preset::named("Energize", 254).creator(lights)
+}
+
+/// Returns a creator for the `Bright` scene with the given lights.
+pub fn bright(lights: &[Light]) -> Creator {
+    BRIGHT.creator(lights)
+}
+
+/// Returns a creator for the `Dimmed` scene with the given lights.
+pub fn dimmed(lights: &[Light]) -> Creator {
+    DIMMED.creator(lights)
+}
+
+/// Returns a creator for the `Nightlight` scene with the given lights.
+pub fn nightlight(lights: &[Light]) -> Creator {
+    NIGHTLIGHT.creator(lights)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn light(id: &str, kind: &str) -> Light {
+        let json = json!({
+            "name": "test",
+            "type": kind,
+            "state": {"on": false, "reachable": true},
+            "modelid": "test",
+            "uniqueid": "test",
+            "swversion": "test"
+        });
+        let light: Light = serde_json::from_value(json).unwrap();
+        Light {
+            id: id.to_owned(),
+            ..light
+        }
+    }
+
+    #[test]
+    fn relax() {
+        let lights = vec![
+            light("1", "Extended color light"),
+            light("2", "Color light"),
+            light("3", "Dimmable light"),
+            light("4", "On/Off plug-in unit"),
+        ];
+        let creator = super::relax(&lights);
+        assert_eq!(creator.name, "Relax");
+        assert_eq!(creator.lights, vec!["1", "2", "3", "4"]);
+        let states = creator.light_states.unwrap();
+        assert_eq!(states["1"].color_temperature, Some(447));
+        assert_eq!(states["1"].color_space_coordinates, None);
+        assert_eq!(states["1"].brightness, Some(144));
+        assert_eq!(states["2"].color_temperature, None);
+        assert_eq!(states["2"].color_space_coordinates, Some((0.5019, 0.4152)));
+        assert_eq!(states["3"].color_temperature, None);
+        assert_eq!(states["3"].brightness, Some(144));
+        assert_eq!(states["4"].brightness, None);
+        assert_eq!(states["4"].on, Some(true));
+    }
+
+    #[test]
+    fn named_presets() {
+        let lights = vec![light("1", "Extended color light")];
+        for (creator, name) in [
+            (super::reading(&lights), "reading"),
+            (super::concentrate(&lights), "concentrate"),
+            (super::energize(&lights), "energize"),
+        ] {
+            let states = creator.light_states.unwrap();
+            assert_eq!(
+                states["1"].color_temperature,
+                Some(color::named(name).unwrap().color_temperature)
+            );
+        }
+    }
+}
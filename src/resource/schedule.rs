@@ -1,8 +1,10 @@
-use crate::resource;
-use chrono::NaiveDateTime;
+use crate::{resource, util};
+use chrono::{NaiveDateTime, NaiveTime};
 use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 use serde_json::{Error as JsonError, Value as JsonValue};
+use std::fmt;
+use std::time::Duration;
 
 /// Schedule of a resource.
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
@@ -18,9 +20,13 @@ pub struct Schedule {
     pub command: Command,
     /// Time when the scheduled event will occur.
     #[serde(rename = "localtime")]
-    pub local_time: String,
+    pub local_time: LocalTime,
     /// UTC time that the timer was started. Only provided for timers.
-    #[serde(rename = "starttime")]
+    #[serde(
+        rename = "starttime",
+        default,
+        deserialize_with = "util::deserialize_option_date_time"
+    )]
     pub start_time: Option<NaiveDateTime>,
     /// Status of the schedule.
     pub status: Status,
@@ -116,6 +122,236 @@ pub enum Status {
     Disabled,
 }
 
+/// Time pattern of a schedule in local time.
+///
+/// Time patterns that can not be parsed are kept as [`LocalTime::Raw`]. Converting a time pattern
+/// into a string with [`ToString`] returns the format that is used by the bridge.
+///
+/// # Examples
+///
+/// ```
+/// use huelib::resource::schedule::LocalTime;
+///
+/// let time = LocalTime::from("W124/T07:00:00");
+/// assert!(matches!(time, LocalTime::Recurring { weekdays: 124, .. }));
+/// assert_eq!(time.to_string(), "W124/T07:00:00");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum LocalTime {
+    /// At a date and time (`YYYY-MM-DDThh:mm:ss[Ahh:mm:ss]`).
+    Absolute {
+        /// Date and time of the event.
+        time: NaiveDateTime,
+        /// Maximal random delay of the event.
+        random: Option<Duration>,
+    },
+    /// At a time on the given weekdays (`W[bbb]/Thh:mm:ss[Ahh:mm:ss]`).
+    Recurring {
+        /// Bitmask of the weekdays, from Monday (`64`) to Sunday (`1`).
+        weekdays: u8,
+        /// Time of the event.
+        time: NaiveTime,
+        /// Maximal random delay of the event.
+        random: Option<Duration>,
+    },
+    /// Once between two times, on the given weekdays or every day
+    /// (`[W[bbb]/]Thh:mm:ss/Thh:mm:ss`).
+    Interval {
+        /// Bitmask of the weekdays, from Monday (`64`) to Sunday (`1`), or `None` for every day.
+        weekdays: Option<u8>,
+        /// Start of the interval.
+        start: NaiveTime,
+        /// End of the interval.
+        end: NaiveTime,
+    },
+    /// After a duration (`PThh:mm:ss[Ahh:mm:ss]`).
+    Timer {
+        /// Duration of the timer.
+        duration: Duration,
+        /// Maximal random delay of the event.
+        random: Option<Duration>,
+    },
+    /// Repeatedly after a duration (`R[nn]/PThh:mm:ss[Ahh:mm:ss]`).
+    RecurringTimer {
+        /// Duration of the timer.
+        duration: Duration,
+        /// Number of repetitions, or `None` to repeat forever.
+        repetitions: Option<u8>,
+        /// Maximal random delay of the event.
+        random: Option<Duration>,
+    },
+    /// A time pattern that could not be parsed.
+    Raw(String),
+}
+
+impl LocalTime {
+    fn parse(value: &str) -> Option<Self> {
+        let (value, random) = match value.split_once('A') {
+            Some((value, random)) => (value, Some(parse_duration(random)?)),
+            None => (value, None),
+        };
+        if let Some(value) = value.strip_prefix('W') {
+            let (weekdays, value) = value.split_once('/')?;
+            let weekdays = parse_weekdays(weekdays)?;
+            let value = value.strip_prefix('T')?;
+            match value.split_once("/T") {
+                Some((start, end)) if random.is_none() => Some(Self::Interval {
+                    weekdays: Some(weekdays),
+                    start: parse_time(start)?,
+                    end: parse_time(end)?,
+                }),
+                Some(_) => None,
+                None => Some(Self::Recurring {
+                    weekdays,
+                    time: parse_time(value)?,
+                    random,
+                }),
+            }
+        } else if let Some(value) = value.strip_prefix('R') {
+            let (repetitions, value) = value.split_once('/')?;
+            let repetitions = match repetitions {
+                "" => None,
+                v => Some(v.parse().ok()?),
+            };
+            Some(Self::RecurringTimer {
+                duration: parse_duration(value.strip_prefix("PT")?)?,
+                repetitions,
+                random,
+            })
+        } else if let Some(value) = value.strip_prefix("PT") {
+            Some(Self::Timer {
+                duration: parse_duration(value)?,
+                random,
+            })
+        } else if let Some(value) = value.strip_prefix('T') {
+            let (start, end) = value.split_once("/T")?;
+            if random.is_some() {
+                return None;
+            }
+            Some(Self::Interval {
+                weekdays: None,
+                start: parse_time(start)?,
+                end: parse_time(end)?,
+            })
+        } else {
+            Some(Self::Absolute {
+                time: NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S").ok()?,
+                random,
+            })
+        }
+    }
+}
+
+impl From<String> for LocalTime {
+    fn from(value: String) -> Self {
+        Self::parse(&value).unwrap_or(Self::Raw(value))
+    }
+}
+
+impl From<&str> for LocalTime {
+    fn from(value: &str) -> Self {
+        Self::from(value.to_owned())
+    }
+}
+
+impl From<LocalTime> for String {
+    fn from(value: LocalTime) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for LocalTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Absolute { time, random } => {
+                write!(f, "{}", time.format("%Y-%m-%dT%H:%M:%S"))?;
+                write_random(f, random)
+            }
+            Self::Recurring {
+                weekdays,
+                time,
+                random,
+            } => {
+                write!(f, "W{:03}/T{}", weekdays, time.format("%H:%M:%S"))?;
+                write_random(f, random)
+            }
+            Self::Interval {
+                weekdays,
+                start,
+                end,
+            } => {
+                if let Some(weekdays) = weekdays {
+                    write!(f, "W{:03}/", weekdays)?;
+                }
+                write!(
+                    f,
+                    "T{}/T{}",
+                    start.format("%H:%M:%S"),
+                    end.format("%H:%M:%S")
+                )
+            }
+            Self::Timer { duration, random } => {
+                write!(f, "PT{}", DisplayDuration(*duration))?;
+                write_random(f, random)
+            }
+            Self::RecurringTimer {
+                duration,
+                repetitions,
+                random,
+            } => {
+                match repetitions {
+                    Some(v) => write!(f, "R{:02}/", v)?,
+                    None => write!(f, "R/")?,
+                }
+                write!(f, "PT{}", DisplayDuration(*duration))?;
+                write_random(f, random)
+            }
+            Self::Raw(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Formats a duration as `hh:mm:ss`.
+struct DisplayDuration(Duration);
+
+impl fmt::Display for DisplayDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.0.as_secs();
+        write!(
+            f,
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
+
+fn write_random(f: &mut fmt::Formatter<'_>, random: &Option<Duration>) -> fmt::Result {
+    match random {
+        Some(v) => write!(f, "A{}", DisplayDuration(*v)),
+        None => Ok(()),
+    }
+}
+
+fn parse_weekdays(value: &str) -> Option<u8> {
+    value.parse().ok().filter(|v| *v <= 127)
+}
+
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M:%S").ok()
+}
+
+fn parse_duration(value: &str) -> Option<Duration> {
+    let mut parts = value.split(':').map(|v| v.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() || minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+    Some(Duration::from_secs(hours * 3600 + minutes * 60 + seconds))
+}
+
 /// Struct for creating a schedule.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
@@ -132,7 +368,7 @@ pub struct Creator {
     /// Sets the local time of the schedule.
     #[serde(rename = "localtime")]
     #[setters(skip)]
-    pub local_time: String,
+    pub local_time: LocalTime,
     /// Sets the status of the schedule.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<Status>,
@@ -146,12 +382,17 @@ pub struct Creator {
 
 impl Creator {
     /// Creates a new [`Creator`].
-    pub fn new(command: Command, local_time: String) -> Self {
+    ///
+    /// The local time can also be given as a time pattern string (see [`LocalTime`]).
+    pub fn new<L>(command: Command, local_time: L) -> Self
+    where
+        L: Into<LocalTime>,
+    {
         Self {
             name: None,
             description: None,
             command,
-            local_time,
+            local_time: local_time.into(),
             status: None,
             auto_delete: None,
             recycle: None,
@@ -160,6 +401,12 @@ impl Creator {
 }
 
 impl resource::Creator for Creator {
+    type Required = (Command, LocalTime);
+
+    fn from_required((command, local_time): Self::Required) -> Self {
+        Self::new(command, local_time)
+    }
+
     fn url_suffix() -> String {
         "schedules".to_owned()
     }
@@ -180,7 +427,8 @@ pub struct Modifier {
     pub command: Option<Command>,
     /// Sets the local time of the schedule.
     #[serde(skip_serializing_if = "Option::is_none", rename = "localtime")]
-    pub local_time: Option<String>,
+    #[setters(into)]
+    pub local_time: Option<LocalTime>,
     /// Sets the status of the schedule.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<Status>,
@@ -269,7 +517,7 @@ mod tests {
             body: json!({"on": true}),
         };
 
-        let creator = Creator::new(command.clone(), "2020-01-01T00:00:00".into());
+        let creator = Creator::new(command.clone(), "2020-01-01T00:00:00");
         let creator_json = serde_json::to_value(creator).unwrap();
         let expected_json = json!({
             "command": {
@@ -318,6 +566,9 @@ mod tests {
         let expected_json = json!({});
         assert_eq!(modifier_json, expected_json);
 
+        let modifier = Modifier::new().with_local_time("2020-01-01T00:00:00");
+        assert_eq!(modifier.local_time, Some("2020-01-01T00:00:00".into()));
+
         let modifier = Modifier {
             name: Some("test".into()),
             description: Some("description test".into()),
@@ -347,4 +598,75 @@ mod tests {
         });
         assert_eq!(modifier_json, expected_json);
     }
+
+    #[test]
+    fn local_time() {
+        let time = |h, m, s| NaiveTime::from_hms_opt(h, m, s).unwrap();
+        let cases = vec![
+            (
+                "2020-01-01T07:30:00",
+                LocalTime::Absolute {
+                    time: chrono::NaiveDate::from_ymd_opt(2020, 1, 1)
+                        .unwrap()
+                        .and_time(time(7, 30, 0)),
+                    random: None,
+                },
+            ),
+            (
+                "W124/T07:00:00A00:15:00",
+                LocalTime::Recurring {
+                    weekdays: 124,
+                    time: time(7, 0, 0),
+                    random: Some(Duration::from_secs(900)),
+                },
+            ),
+            (
+                "W003/T10:00:00/T12:00:00",
+                LocalTime::Interval {
+                    weekdays: Some(3),
+                    start: time(10, 0, 0),
+                    end: time(12, 0, 0),
+                },
+            ),
+            (
+                "T22:00:00/T23:30:00",
+                LocalTime::Interval {
+                    weekdays: None,
+                    start: time(22, 0, 0),
+                    end: time(23, 30, 0),
+                },
+            ),
+            (
+                "PT00:10:00",
+                LocalTime::Timer {
+                    duration: Duration::from_secs(600),
+                    random: None,
+                },
+            ),
+            (
+                "R05/PT01:00:00A00:00:30",
+                LocalTime::RecurringTimer {
+                    duration: Duration::from_secs(3600),
+                    repetitions: Some(5),
+                    random: Some(Duration::from_secs(30)),
+                },
+            ),
+            (
+                "R/PT00:00:05",
+                LocalTime::RecurringTimer {
+                    duration: Duration::from_secs(5),
+                    repetitions: None,
+                    random: None,
+                },
+            ),
+            ("W200/T07:00:00", LocalTime::Raw("W200/T07:00:00".into())),
+            ("PT00:61:00", LocalTime::Raw("PT00:61:00".into())),
+            ("sunrise", LocalTime::Raw("sunrise".into())),
+        ];
+        for (value, expected) in cases {
+            let local_time: LocalTime = serde_json::from_value(json!(value)).unwrap();
+            assert_eq!(local_time, expected);
+            assert_eq!(serde_json::to_value(local_time).unwrap(), json!(value));
+        }
+    }
 }
@@ -1,8 +1,9 @@
 #![allow(clippy::needless_update)]
 
-use crate::{resource, util};
+use crate::{resource, util, Error};
 use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// A sensor.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize)]
@@ -45,6 +46,16 @@ impl Sensor {
     pub(crate) fn with_id(self, id: String) -> Self {
         Self { id, ..self }
     }
+
+    /// Returns whether the sensor is a motion sensor that reports presence.
+    ///
+    /// This is the case for sensors of the type `ZLLPresence`, whose state contains the
+    /// [`presence`] attribute.
+    ///
+    /// [`presence`]: State::presence
+    pub fn is_presence_sensor(&self) -> bool {
+        self.type_name == "ZLLPresence"
+    }
 }
 
 impl resource::Resource for Sensor {}
@@ -53,41 +64,202 @@ impl resource::Resource for Sensor {}
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub struct State {
     /// Whether the sensor is present.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub presence: Option<bool>,
     /// Flag of the sensor.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub flag: Option<bool>,
-    /// The current battery state in percent.
+    /// Time when the state was last updated, in UTC.
+    ///
+    /// This is `None` if the sensor has not reported a state yet.
     #[serde(
         rename = "lastupdated",
+        default,
         deserialize_with = "util::deserialize_option_date_time"
     )]
     pub last_updated: Option<chrono::NaiveDateTime>,
-    /// Button id that was pressed last.
+    /// Button event that occurred last.
     #[serde(rename = "buttonevent")]
-    pub button_event: Option<u32>,
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
+    pub button_event: Option<ButtonEvent>,
     /// The temperature in centigrades.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub temperature: Option<i32>,
     /// The light level in centiluxes.
     #[serde(rename = "lightlevel")]
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub light_level: Option<u32>,
     /// Whether it's dark according to the sensor's sensitivity.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub dark: Option<bool>,
     /// Whether it's daytime according to the sensor's sensitivity.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub daylight: Option<bool>,
     // TODO: Add missing attributes (https://github.com/yuqio/huelib-rs/issues/2)
 }
 
+/// Event of a button of a switch.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
+#[serde(from = "u16")]
+pub enum ButtonEvent {
+    /// A button was pressed or released.
+    Button {
+        /// The button that caused the event.
+        button: Button,
+        /// The action that was performed with the button.
+        action: ButtonAction,
+    },
+    /// An event code that is not known.
+    Unknown(u16),
+}
+
+impl From<u16> for ButtonEvent {
+    fn from(value: u16) -> Self {
+        let (button, action) = match value {
+            // Events of the Hue tap switch, which only reports released buttons.
+            34 => (1, 2),
+            16 => (2, 2),
+            17 => (3, 2),
+            18 => (4, 2),
+            // Events of the Hue dimmer switch and the Hue tap dial switch.
+            v => (v / 1000, v % 1000),
+        };
+        match (Button::from_code(button), ButtonAction::from_code(action)) {
+            (Some(button), Some(action)) => Self::Button { button, action },
+            _ => Self::Unknown(value),
+        }
+    }
+}
+
+impl FromStr for ButtonEvent {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<u16>().map(Self::from)
+    }
+}
+
+/// Button of a switch.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Button {
+    /// The first button, which is the on button of a Hue dimmer switch.
+    One,
+    /// The second button, which is the brighten button of a Hue dimmer switch.
+    Two,
+    /// The third button, which is the dim button of a Hue dimmer switch.
+    Three,
+    /// The fourth button, which is the off button of a Hue dimmer switch.
+    Four,
+}
+
+impl Button {
+    fn from_code(code: u16) -> Option<Self> {
+        match code {
+            1 => Some(Self::One),
+            2 => Some(Self::Two),
+            3 => Some(Self::Three),
+            4 => Some(Self::Four),
+            _ => None,
+        }
+    }
+}
+
+/// Action that was performed with a button.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ButtonAction {
+    /// The button was pressed.
+    InitialPress,
+    /// The button is held down.
+    Hold,
+    /// The button was released after a short press.
+    ShortRelease,
+    /// The button was released after being held down.
+    LongRelease,
+}
+
+impl ButtonAction {
+    fn from_code(code: u16) -> Option<Self> {
+        match code {
+            0 => Some(Self::InitialPress),
+            1 => Some(Self::Hold),
+            2 => Some(Self::ShortRelease),
+            3 => Some(Self::LongRelease),
+            _ => None,
+        }
+    }
+}
+
 /// Configuration of a sensor.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub struct Config {
     /// Whether the sensor is on.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient")
+    )]
     pub on: bool,
     /// Whether the sensor can be reached by the bridge.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub reachable: Option<bool>,
     /// The current battery state in percent.
     ///
     /// Only for battery powered devices. Not present when not provided on creation (CLIP sensors).
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
     pub battery: Option<u8>,
+    /// Sensitivity of the sensor.
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
+    pub sensitivity: Option<u8>,
+    /// Maximal sensitivity that is supported by the sensor.
+    #[serde(rename = "sensitivitymax")]
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
+    pub sensitivity_max: Option<u8>,
+    /// Whether the LED of the sensor indicates activity.
+    #[serde(rename = "ledindication")]
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
+    pub led_indication: Option<bool>,
+    /// Whether the sensor is in test mode.
+    #[serde(rename = "usertest")]
+    #[cfg_attr(
+        feature = "lenient",
+        serde(default, deserialize_with = "util::deserialize_lenient_option")
+    )]
+    pub user_test: Option<bool>,
 }
 
 /// Modifier for sensor attributes.
@@ -113,6 +285,84 @@ impl resource::Modifier for AttributeModifier {
     }
 }
 
+/// Struct for creating a CLIP sensor.
+///
+/// CLIP sensors are virtual sensors whose state is set with requests, e.g. from rules or
+/// schedules.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Setters)]
+#[setters(strip_option, prefix = "with_")]
+pub struct Creator {
+    /// Sets the name of the sensor.
+    #[setters(skip)]
+    pub name: String,
+    /// Sets the type name of the sensor, e.g. `CLIPGenericFlag`.
+    #[serde(rename = "type")]
+    #[setters(skip)]
+    pub type_name: String,
+    /// Sets the model identifier of the sensor.
+    #[serde(rename = "modelid")]
+    #[setters(skip)]
+    pub model_id: String,
+    /// Sets the software version of the sensor.
+    #[serde(rename = "swversion")]
+    #[setters(skip)]
+    pub software_version: String,
+    /// Sets the unique identifier of the sensor.
+    #[serde(rename = "uniqueid")]
+    #[setters(skip)]
+    pub unique_id: String,
+    /// Sets the manufacturer name of the sensor.
+    #[serde(rename = "manufacturername")]
+    #[setters(skip)]
+    pub manufacturer_name: String,
+    /// Sets whether the sensor is automatically deleted when not referenced anymore.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recycle: Option<bool>,
+}
+
+impl Creator {
+    /// Creates a new [`Creator`].
+    pub fn new(
+        name: String,
+        type_name: String,
+        model_id: String,
+        software_version: String,
+        unique_id: String,
+        manufacturer_name: String,
+    ) -> Self {
+        Self {
+            name,
+            type_name,
+            model_id,
+            software_version,
+            unique_id,
+            manufacturer_name,
+            recycle: None,
+        }
+    }
+}
+
+impl resource::Creator for Creator {
+    type Required = (String, String, String, String, String, String);
+
+    fn from_required(
+        (name, type_name, model_id, software_version, unique_id, manufacturer_name): Self::Required,
+    ) -> Self {
+        Self::new(
+            name,
+            type_name,
+            model_id,
+            software_version,
+            unique_id,
+            manufacturer_name,
+        )
+    }
+
+    fn url_suffix() -> String {
+        "sensors".to_owned()
+    }
+}
+
 /// Modifier for the sensor state.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
@@ -120,6 +370,11 @@ pub struct StateModifier {
     /// Sets the presence of the sensor.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub presence: Option<bool>,
+    /// Sets the flag of the sensor.
+    ///
+    /// Only supported by sensors with the type `CLIPGenericFlag`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flag: Option<bool>,
 }
 
 impl StateModifier {
@@ -143,6 +398,39 @@ pub struct ConfigModifier {
     /// Sets whether the sensor is on.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on: Option<bool>,
+    /// Sets the sensitivity of the sensor.
+    ///
+    /// The value must not be greater than the [`sensitivity_max`] of the sensor.
+    ///
+    /// [`sensitivity_max`]: Config::sensitivity_max
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitivity: Option<u8>,
+    /// Sets whether the LED of the sensor indicates activity.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "ledindication")]
+    pub led_indication: Option<bool>,
+    /// Sets whether the sensor is in test mode.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "usertest")]
+    pub user_test: Option<bool>,
+    /// Sets the latitude of the location, only used by the daylight sensor.
+    ///
+    /// The value must be in the format of the Philips Hue API (e.g. `48.7784N`), see
+    /// [`format_latitude`] and [`ConfigModifier::with_coordinates`].
+    #[serde(skip_serializing_if = "Option::is_none", rename = "lat")]
+    pub latitude: Option<String>,
+    /// Sets the longitude of the location, only used by the daylight sensor.
+    ///
+    /// The value must be in the format of the Philips Hue API (e.g. `009.1800E`), see
+    /// [`format_longitude`] and [`ConfigModifier::with_coordinates`].
+    #[serde(skip_serializing_if = "Option::is_none", rename = "long")]
+    pub longitude: Option<String>,
+    /// Sets the offset to the sunrise in minutes, between -120 and 120. Only used by the daylight
+    /// sensor.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "sunriseoffset")]
+    pub sunrise_offset: Option<i8>,
+    /// Sets the offset to the sunset in minutes, between -120 and 120. Only used by the daylight
+    /// sensor.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "sunsetoffset")]
+    pub sunset_offset: Option<i8>,
 }
 
 impl ConfigModifier {
@@ -150,6 +438,53 @@ impl ConfigModifier {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Sets the latitude and longitude of the location in decimal degrees.
+    ///
+    /// Positive values are north and east, negative values are south and west.
+    pub fn with_coordinates(self, latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude: Some(format_latitude(latitude)),
+            longitude: Some(format_longitude(longitude)),
+            ..self
+        }
+    }
+
+    /// Checks whether the values of this modifier are supported by a sensor with the given
+    /// configuration.
+    ///
+    /// Returns [`Error::ValueOutOfRange`] if the sensitivity is greater than the maximal
+    /// sensitivity of the sensor, or if the sunrise or sunset offset is not between -120 and 120.
+    ///
+    /// [`Error::ValueOutOfRange`]: crate::Error::ValueOutOfRange
+    pub fn validate(&self, config: &Config) -> crate::Result<()> {
+        if let (Some(sensitivity), Some(max)) = (self.sensitivity, config.sensitivity_max) {
+            if sensitivity > max {
+                return Err(Error::ValueOutOfRange {
+                    attribute: "sensitivity",
+                    value: sensitivity.into(),
+                    min: 0,
+                    max: max.into(),
+                });
+            }
+        }
+        for (attribute, offset) in [
+            ("sunriseoffset", self.sunrise_offset),
+            ("sunsetoffset", self.sunset_offset),
+        ] {
+            if let Some(offset) = offset {
+                if !(-120..=120).contains(&offset) {
+                    return Err(Error::ValueOutOfRange {
+                        attribute,
+                        value: offset.into(),
+                        min: -120,
+                        max: 120,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl resource::Modifier for ConfigModifier {
@@ -159,7 +494,40 @@ impl resource::Modifier for ConfigModifier {
     }
 }
 
-/// Scanner for new lights.
+/// Converts a latitude in decimal degrees into the format of the Philips Hue API.
+///
+/// # Examples
+///
+/// ```
+/// use huelib::resource::sensor::format_latitude;
+///
+/// assert_eq!(format_latitude(48.7784), "48.7784N");
+/// assert_eq!(format_latitude(-33.8688), "33.8688S");
+/// ```
+pub fn format_latitude(value: f64) -> String {
+    let direction = if value < 0.0 { 'S' } else { 'N' };
+    format!("{:07.4}{}", value.abs().min(90.0), direction)
+}
+
+/// Converts a longitude in decimal degrees into the format of the Philips Hue API.
+///
+/// # Examples
+///
+/// ```
+/// use huelib::resource::sensor::format_longitude;
+///
+/// assert_eq!(format_longitude(9.18), "009.1800E");
+/// assert_eq!(format_longitude(-122.4194), "122.4194W");
+/// ```
+pub fn format_longitude(value: f64) -> String {
+    let direction = if value < 0.0 { 'W' } else { 'E' };
+    format!("{:08.4}{}", value.abs().min(180.0), direction)
+}
+
+/// Scanner for new sensors.
+///
+/// If no device identifiers are set, the body of the request is an empty object and the bridge
+/// searches for all new sensors.
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash, Serialize, Setters)]
 #[setters(strip_option, prefix = "with_")]
 pub struct Scanner {
@@ -186,6 +554,71 @@ mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn deserialize_state() {
+        let state: State =
+            serde_json::from_value(json!({"presence": true, "lastupdated": "none"})).unwrap();
+        assert_eq!(state.last_updated, None);
+
+        let state: State = serde_json::from_value(json!({"daylight": true})).unwrap();
+        assert_eq!(state.last_updated, None);
+
+        let state: State =
+            serde_json::from_value(json!({"lastupdated": "2020-01-02T03:04:05Z"})).unwrap();
+        let date = chrono::NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+        let time = chrono::NaiveTime::from_hms_opt(3, 4, 5).unwrap();
+        assert_eq!(
+            state.last_updated,
+            Some(chrono::NaiveDateTime::new(date, time))
+        );
+    }
+
+    #[test]
+    fn deserialize_presence_sensor() {
+        let sensor: Sensor = serde_json::from_value(json!({
+            "name": "Hue motion sensor 1",
+            "type": "ZLLPresence",
+            "modelid": "SML001",
+            "state": {"presence": true, "lastupdated": "2020-01-01T00:00:00"},
+            "config": {"on": true, "reachable": true, "battery": 100, "sensitivity": 2}
+        }))
+        .unwrap();
+        assert!(sensor.is_presence_sensor());
+        assert_eq!(sensor.state.presence, Some(true));
+    }
+
+    #[test]
+    fn deserialize_button_event() {
+        let event: ButtonEvent = serde_json::from_value(json!(1002)).unwrap();
+        assert_eq!(
+            event,
+            ButtonEvent::Button {
+                button: Button::One,
+                action: ButtonAction::ShortRelease
+            }
+        );
+        let event: ButtonEvent = serde_json::from_value(json!(4001)).unwrap();
+        assert_eq!(
+            event,
+            ButtonEvent::Button {
+                button: Button::Four,
+                action: ButtonAction::Hold
+            }
+        );
+        let event: ButtonEvent = serde_json::from_value(json!(17)).unwrap();
+        assert_eq!(
+            event,
+            ButtonEvent::Button {
+                button: Button::Three,
+                action: ButtonAction::ShortRelease
+            }
+        );
+        let event: ButtonEvent = serde_json::from_value(json!(5002)).unwrap();
+        assert_eq!(event, ButtonEvent::Unknown(5002));
+        let event: ButtonEvent = serde_json::from_value(json!(1005)).unwrap();
+        assert_eq!(event, ButtonEvent::Unknown(1005));
+    }
+
     #[test]
     fn serialize_attribute_modifier() {
         let modifier = AttributeModifier::new();
@@ -210,12 +643,37 @@ mod tests {
 
         let modifier = StateModifier {
             presence: Some(true),
+            flag: Some(false),
         };
         let modifier_json = serde_json::to_value(modifier).unwrap();
-        let expected_json = json!({"presence": true});
+        let expected_json = json!({"presence": true, "flag": false});
         assert_eq!(modifier_json, expected_json);
     }
 
+    #[test]
+    fn serialize_creator() {
+        let creator = Creator::new(
+            "test".into(),
+            "CLIPGenericFlag".into(),
+            "model".into(),
+            "1.0".into(),
+            "unique".into(),
+            "manufacturer".into(),
+        )
+        .with_recycle(true);
+        let creator_json = serde_json::to_value(creator).unwrap();
+        let expected_json = json!({
+            "name": "test",
+            "type": "CLIPGenericFlag",
+            "modelid": "model",
+            "swversion": "1.0",
+            "uniqueid": "unique",
+            "manufacturername": "manufacturer",
+            "recycle": true
+        });
+        assert_eq!(creator_json, expected_json);
+    }
+
     #[test]
     fn serialize_config_modifier() {
         let modifier = ConfigModifier::new();
@@ -223,12 +681,88 @@ mod tests {
         let expected_json = json!({});
         assert_eq!(modifier_json, expected_json);
 
-        let modifier = ConfigModifier { on: Some(true) };
+        let modifier = ConfigModifier {
+            on: Some(true),
+            sensitivity: Some(2),
+            led_indication: Some(false),
+            user_test: Some(true),
+            latitude: None,
+            longitude: None,
+            sunrise_offset: Some(-30),
+            sunset_offset: Some(15),
+        };
         let modifier_json = serde_json::to_value(modifier).unwrap();
-        let expected_json = json!({"on": true});
+        let expected_json = json!({
+            "on": true,
+            "sensitivity": 2,
+            "ledindication": false,
+            "usertest": true,
+            "sunriseoffset": -30,
+            "sunsetoffset": 15
+        });
+        assert_eq!(modifier_json, expected_json);
+
+        let modifier = ConfigModifier::new().with_coordinates(48.7784, 9.18);
+        let modifier_json = serde_json::to_value(modifier).unwrap();
+        let expected_json = json!({
+            "lat": "48.7784N",
+            "long": "009.1800E"
+        });
         assert_eq!(modifier_json, expected_json);
     }
 
+    #[test]
+    fn format_coordinates() {
+        assert_eq!(format_latitude(0.0), "00.0000N");
+        assert_eq!(format_latitude(-5.5), "05.5000S");
+        assert_eq!(format_longitude(180.0), "180.0000E");
+        assert_eq!(format_longitude(-0.12345), "000.1235W");
+    }
+
+    #[test]
+    fn validate_config_modifier() {
+        let config: Config = serde_json::from_value(json!({
+            "on": true,
+            "sensitivity": 2,
+            "sensitivitymax": 2
+        }))
+        .unwrap();
+        assert!(ConfigModifier::new().validate(&config).is_ok());
+        assert!(ConfigModifier::new()
+            .with_sensitivity(2)
+            .validate(&config)
+            .is_ok());
+        assert!(ConfigModifier::new()
+            .with_sensitivity(3)
+            .validate(&config)
+            .is_err());
+        assert!(ConfigModifier::new()
+            .with_sunrise_offset(-120)
+            .with_sunset_offset(120)
+            .validate(&config)
+            .is_ok());
+        assert!(matches!(
+            ConfigModifier::new()
+                .with_sunrise_offset(-121)
+                .validate(&config),
+            Err(Error::ValueOutOfRange {
+                attribute: "sunriseoffset",
+                value: -121,
+                ..
+            })
+        ));
+        assert!(matches!(
+            ConfigModifier::new()
+                .with_sunset_offset(121)
+                .validate(&config),
+            Err(Error::ValueOutOfRange {
+                attribute: "sunsetoffset",
+                value: 121,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn serialize_scanner() {
         let scanner = Scanner::new();
@@ -73,6 +73,7 @@ pub enum ErrorKind {
     GroupTableIsFull = 301,
     UpdateOrDeleteGroupOfThisTypeNotAllowed = 305,
     LightAlreadyUsedInAnotherRoom = 306,
+    CannotClaimStreamOwnership = 307,
     SceneCouldNotBeCreatedBufferIsFull = 402,
     SceneCouldNotBeRemoved = 403,
     SceneCouldNotBeCreatedGroupIsEmpty = 404,
@@ -105,6 +106,47 @@ pub struct Modified {
     pub value: JsonValue,
 }
 
+impl Modified {
+    /// Splits the address into the kind and identifier of the resource and the attribute.
+    ///
+    /// For example the address `/lights/1/state/on` is split into the kind `lights`, the
+    /// identifier `1` and the attribute `state/on`. The configuration has no identifier, so
+    /// `/config/name` is split into the kind `config` and the attribute `name`.
+    pub fn parsed_address(&self) -> Address {
+        let mut parts = self.address.trim_start_matches('/').splitn(2, '/');
+        let kind = parts.next().unwrap_or_default().to_owned();
+        let rest = parts.next().unwrap_or_default();
+        let (id, attribute) = if kind == "config" {
+            (None, rest)
+        } else {
+            let mut parts = rest.splitn(2, '/');
+            let id = parts
+                .next()
+                .filter(|v| !v.is_empty())
+                .map(ToOwned::to_owned);
+            (id, parts.next().unwrap_or_default())
+        };
+        Address {
+            kind,
+            id,
+            attribute: attribute.to_owned(),
+        }
+    }
+}
+
+/// Address of an attribute that was modified.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Address {
+    /// Kind of the resource, e.g. `lights` or `groups`.
+    pub kind: String,
+    /// Identifier of the resource.
+    ///
+    /// This is `None` for resources that only exist once, like the configuration.
+    pub id: Option<String>,
+    /// Path of the attribute in the resource, e.g. `state/on`.
+    pub attribute: String,
+}
+
 impl fmt::Display for Modified {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Set '{}' to {}", self.address, self.value)
@@ -171,6 +213,16 @@ mod tests {
             description: "description test".to_owned(),
         };
         assert_eq!(response, Response::Error(error));
+        let json = json!({
+            "error": {
+                "type": 307,
+                "address": "/groups/1/stream/active",
+                "description": "Cannot claim stream ownership",
+            }
+        });
+        let response: Response<String> = serde_json::from_value(json).unwrap();
+        let error = response.into_result().unwrap_err();
+        assert_eq!(error.kind, ErrorKind::CannotClaimStreamOwnership);
     }
 
     #[test]
@@ -187,4 +239,29 @@ mod tests {
         };
         assert_eq!(response, Response::Success(modified));
     }
+
+    #[test]
+    fn modified_parsed_address() {
+        let modified = Modified {
+            address: "/lights/1/state/on".into(),
+            value: json!(true),
+        };
+        let address = Address {
+            kind: "lights".into(),
+            id: Some("1".into()),
+            attribute: "state/on".into(),
+        };
+        assert_eq!(modified.parsed_address(), address);
+
+        let modified = Modified {
+            address: "/config/name".into(),
+            value: json!("test"),
+        };
+        let address = Address {
+            kind: "config".into(),
+            id: None,
+            attribute: "name".into(),
+        };
+        assert_eq!(modified.parsed_address(), address);
+    }
 }
@@ -0,0 +1,95 @@
+use crate::resource::{group, Adjust, Group, Scene};
+use crate::{response::Modified, Bridge, Error, Response, Result};
+
+/// A group together with the scenes that are linked to it.
+///
+/// This is a convenience layer over [`Bridge::get_group`], [`Bridge::get_all_scenes`] and
+/// [`Bridge::set_group_state`]. The group and scenes are fetched once when the room is created
+/// and are not updated afterwards.
+///
+/// # Examples
+///
+/// Recall the scene `Relax` in the room with the identifier `1`:
+/// ```no_run
+/// # fn main() -> huelib::Result<()> {
+/// # use huelib::Bridge;
+/// # use std::net::{IpAddr, Ipv4Addr};
+/// # let bridge = Bridge::new(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2)), String::new());
+/// // let bridge = Bridge::new(...);
+/// let room = bridge.get_room("1")?;
+/// room.recall_scene("Relax")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Room<'a> {
+    bridge: &'a Bridge,
+    group: Group,
+    scenes: Vec<Scene>,
+}
+
+impl<'a> Room<'a> {
+    /// Fetches the group with the given identifier and the scenes that are linked to it.
+    pub fn get<S>(bridge: &'a Bridge, id: S) -> Result<Self>
+    where
+        S: Into<String>,
+    {
+        let group = bridge.get_group(id)?;
+        let scenes = bridge
+            .get_all_scenes()?
+            .into_iter()
+            .filter(|scene| scene.group.as_ref() == Some(&group.id))
+            .collect();
+        Ok(Self {
+            bridge,
+            group,
+            scenes,
+        })
+    }
+
+    /// Returns the group of the room.
+    pub fn group(&self) -> &Group {
+        &self.group
+    }
+
+    /// Returns the scenes that are linked to the group of the room.
+    pub fn scenes(&self) -> &[Scene] {
+        &self.scenes
+    }
+
+    /// Returns the scene with the given name.
+    pub fn scene<S>(&self, name: S) -> Option<&Scene>
+    where
+        S: AsRef<str>,
+    {
+        self.scenes.iter().find(|scene| scene.name == name.as_ref())
+    }
+
+    /// Recalls the scene with the given name.
+    ///
+    /// Returns [`Error::NameNotFound`] if the room has no scene with the name.
+    pub fn recall_scene<S>(&self, name: S) -> Result<Vec<Response<Modified>>>
+    where
+        S: AsRef<str>,
+    {
+        let scene = self
+            .scene(name.as_ref())
+            .ok_or_else(|| Error::NameNotFound(name.as_ref().to_owned()))?;
+        self.set_state(&group::StateModifier::new().with_scene(scene.id.clone()))
+    }
+
+    /// Turns all lights of the room on or off.
+    pub fn set_on(&self, on: bool) -> Result<Vec<Response<Modified>>> {
+        self.set_state(&group::StateModifier::new().with_on(on))
+    }
+
+    /// Sets the brightness of all lights of the room.
+    pub fn set_brightness(&self, brightness: Adjust<u8>) -> Result<Vec<Response<Modified>>> {
+        self.set_state(&group::StateModifier::new().with_brightness(brightness))
+    }
+
+    /// Modifies the state of the group of the room.
+    pub fn set_state(&self, modifier: &group::StateModifier) -> Result<Vec<Response<Modified>>> {
+        self.bridge.set_group_state(self.group.id.clone(), modifier)
+    }
+}
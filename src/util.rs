@@ -1,5 +1,17 @@
-use chrono::{NaiveDateTime, NaiveTime};
+use chrono::{DateTime, NaiveDateTime, NaiveTime, ParseError};
 use serde::de::{Deserialize, Deserializer, Error};
+use std::net::IpAddr;
+#[cfg(feature = "lenient")]
+use std::{fmt::Display, str::FromStr};
+
+/// Parses a date and time as it is sent by the bridge.
+///
+/// Accepts the bare `%Y-%m-%dT%H:%M:%S` format with optional fractional seconds, and falls back
+/// to RFC 3339 (e.g. with a `Z` suffix) which is converted to UTC.
+pub(crate) fn parse_date_time(value: &str) -> Result<NaiveDateTime, ParseError> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| DateTime::parse_from_rfc3339(value).map(|v| v.naive_utc()))
+}
 
 pub(crate) fn deserialize_option_string<'de, D: Deserializer<'de>>(
     deserializer: D,
@@ -11,15 +23,30 @@ pub(crate) fn deserialize_option_string<'de, D: Deserializer<'de>>(
     })
 }
 
+pub(crate) fn deserialize_option_ip_addr<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<IpAddr>, D::Error> {
+    let value: Option<String> = Deserialize::deserialize(deserializer)?;
+    Ok(match value.as_deref() {
+        Some("none") | None => None,
+        Some(v) => Some(v.parse().map_err(D::Error::custom)?),
+    })
+}
+
+pub(crate) fn deserialize_date_time<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<NaiveDateTime, D::Error> {
+    let value: String = Deserialize::deserialize(deserializer)?;
+    parse_date_time(&value).map_err(D::Error::custom)
+}
+
 pub(crate) fn deserialize_option_date_time<'de, D: Deserializer<'de>>(
     deserializer: D,
 ) -> Result<Option<NaiveDateTime>, D::Error> {
     let value: Option<String> = Deserialize::deserialize(deserializer)?;
     Ok(match value.as_deref() {
         Some("none") | None => None,
-        Some(v) => {
-            Some(NaiveDateTime::parse_from_str(v, "%Y-%m-%dT%H:%M:%S").map_err(D::Error::custom)?)
-        }
+        Some(v) => Some(parse_date_time(v).map_err(D::Error::custom)?),
     })
 }
 
@@ -33,6 +60,42 @@ pub(crate) fn deserialize_option_time<'de, D: Deserializer<'de>>(
     })
 }
 
+/// Value that is either encoded as itself or as a string.
+#[cfg(feature = "lenient")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum Lenient<T> {
+    Value(T),
+    String(String),
+}
+
+#[cfg(feature = "lenient")]
+pub(crate) fn deserialize_lenient<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + FromStr,
+    T::Err: Display,
+{
+    match Lenient::deserialize(deserializer)? {
+        Lenient::Value(v) => Ok(v),
+        Lenient::String(v) => v.parse().map_err(D::Error::custom),
+    }
+}
+
+#[cfg(feature = "lenient")]
+pub(crate) fn deserialize_lenient_option<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + FromStr,
+    T::Err: Display,
+{
+    match Option::<Lenient<T>>::deserialize(deserializer)? {
+        Some(Lenient::Value(v)) => Ok(Some(v)),
+        Some(Lenient::String(v)) => v.parse().map(Some).map_err(D::Error::custom),
+        None => Ok(None),
+    }
+}
+
 macro_rules! custom_serialize {
     ($serializer:expr, $struct_name:expr; $($k:ident => ($($v:tt)*),)*) => {
         let mut len = 0;
@@ -79,6 +142,7 @@ macro_rules! custom_serialize {
 mod tests {
     use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
     use serde_json::json;
+    use std::net::{IpAddr, Ipv4Addr};
 
     #[test]
     fn deserialize_option_string() {
@@ -95,6 +159,58 @@ mod tests {
         assert_eq!(value, Some("test".to_owned()));
     }
 
+    #[test]
+    fn deserialize_option_ip_addr() {
+        let json = json!("none");
+        let value = super::deserialize_option_ip_addr(json).unwrap();
+        assert_eq!(value, None);
+
+        let json = json!("192.168.2.1");
+        let value = super::deserialize_option_ip_addr(json).unwrap();
+        assert_eq!(value, Some(IpAddr::V4(Ipv4Addr::new(192, 168, 2, 1))));
+
+        let json = json!("proxy");
+        assert!(super::deserialize_option_ip_addr(json).is_err());
+    }
+
+    #[test]
+    fn parse_date_time() {
+        let date = NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+        let time = NaiveTime::from_hms_opt(3, 4, 5).unwrap();
+        let expected = NaiveDateTime::new(date, time);
+        assert_eq!(super::parse_date_time("2020-01-02T03:04:05"), Ok(expected));
+        assert_eq!(super::parse_date_time("2020-01-02T03:04:05Z"), Ok(expected));
+        assert_eq!(
+            super::parse_date_time("2020-01-02T05:04:05+02:00"),
+            Ok(expected)
+        );
+
+        let time = NaiveTime::from_hms_milli_opt(3, 4, 5, 250).unwrap();
+        let expected = NaiveDateTime::new(date, time);
+        assert_eq!(
+            super::parse_date_time("2020-01-02T03:04:05.250"),
+            Ok(expected)
+        );
+        assert_eq!(
+            super::parse_date_time("2020-01-02T03:04:05.250Z"),
+            Ok(expected)
+        );
+
+        assert!(super::parse_date_time("2020-01-02").is_err());
+    }
+
+    #[test]
+    fn deserialize_date_time() {
+        let json = json!("2020-01-02T03:04:05Z");
+        let value = super::deserialize_date_time(json).unwrap();
+        let date = NaiveDate::from_ymd_opt(2020, 1, 2).unwrap();
+        let time = NaiveTime::from_hms_opt(3, 4, 5).unwrap();
+        assert_eq!(value, NaiveDateTime::new(date, time));
+
+        let json = json!("none");
+        assert!(super::deserialize_date_time(json).is_err());
+    }
+
     #[test]
     fn deserialize_option_date_time() {
         let json = json!("none");
@@ -107,11 +223,28 @@ mod tests {
 
         let json = json!("2020-01-01T01:30:00");
         let value = super::deserialize_option_date_time(json).unwrap();
-        let date = NaiveDate::from_ymd(2020, 1, 1);
-        let time = NaiveTime::from_hms(1, 30, 0);
+        let date = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let time = NaiveTime::from_hms_opt(1, 30, 0).unwrap();
         assert_eq!(value, Some(NaiveDateTime::new(date, time)));
     }
 
+    #[cfg(feature = "lenient")]
+    #[test]
+    fn deserialize_lenient() {
+        let value: bool = super::deserialize_lenient(json!(true)).unwrap();
+        assert!(value);
+        let value: bool = super::deserialize_lenient(json!("true")).unwrap();
+        assert!(value);
+        let value: u8 = super::deserialize_lenient(json!("254")).unwrap();
+        assert_eq!(value, 254);
+        assert!(super::deserialize_lenient::<_, u8>(json!("test")).is_err());
+
+        let value: Option<u16> = super::deserialize_lenient_option(json!(null)).unwrap();
+        assert_eq!(value, None);
+        let value: Option<u16> = super::deserialize_lenient_option(json!("366")).unwrap();
+        assert_eq!(value, Some(366));
+    }
+
     #[test]
     fn deserialize_option_time() {
         let json = json!("none");
@@ -124,6 +257,6 @@ mod tests {
 
         let json = json!("T02:00:20");
         let value = super::deserialize_option_time(json).unwrap();
-        assert_eq!(value, Some(NaiveTime::from_hms(2, 0, 20)));
+        assert_eq!(value, Some(NaiveTime::from_hms_opt(2, 0, 20).unwrap()));
     }
 }
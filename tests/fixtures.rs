@@ -0,0 +1,139 @@
+//! Deserializes responses that were recorded from real bridges.
+//!
+//! New fixtures can be added to the `fixtures` directory when a response of a bridge cannot be
+//! deserialized.
+
+use huelib::resource::{
+    config, group, light, scene, Capabilities, Config, Group, Light, Scene, Sensor,
+};
+use std::collections::HashMap;
+
+fn parse<T>(json: &str) -> HashMap<String, T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn lights() {
+    let lights: HashMap<String, Light> = parse(include_str!("fixtures/lights.json"));
+    assert_eq!(lights.len(), 2);
+
+    let light = &lights["1"];
+    assert_eq!(light.name, "Hue color lamp 1");
+    assert_eq!(light.kind, light::Kind::ExtendedColorLight);
+    assert!(light.is_on());
+    assert!(light.is_reachable());
+    assert_eq!(light.state.brightness, Some(144));
+    assert_eq!(light.state.color_temperature, Some(443));
+    #[cfg(not(feature = "old-api"))]
+    {
+        let gamut = light.color_gamut().unwrap();
+        assert_eq!(gamut.kind, light::ColorGamutType::C);
+        assert_eq!(
+            light.software_update.as_ref().unwrap().state,
+            light::SoftwareUpdateState::NoUpdates
+        );
+    }
+
+    let light = &lights["2"];
+    assert_eq!(light.kind, light::Kind::DimmableLight);
+    assert!(!light.is_on());
+    assert!(!light.is_reachable());
+    assert!(light.supports_dimming());
+    assert!(!light.supports_color());
+    #[cfg(not(feature = "old-api"))]
+    assert_eq!(light.color_gamut(), None);
+}
+
+#[test]
+fn groups() {
+    let groups: HashMap<String, Group> = parse(include_str!("fixtures/groups.json"));
+    assert_eq!(groups.len(), 2);
+
+    let group = &groups["1"];
+    assert_eq!(group.name, "Living room");
+    assert_eq!(
+        group.kind,
+        group::Kind::Creatable(group::CreatableKind::Room)
+    );
+    assert_eq!(group.class, Some(group::Class::LivingRoom));
+    assert_eq!(group.lights, vec!["1", "2"]);
+    assert!(group.any_on());
+    assert!(!group.all_on());
+
+    let group = &groups["2"];
+    assert_eq!(
+        group.kind,
+        group::Kind::Creatable(group::CreatableKind::Entertainment)
+    );
+    assert_eq!(group.locations.as_ref().unwrap()["1"], [-0.5, 0.8, 0.0]);
+}
+
+#[test]
+fn scenes() {
+    let scenes: HashMap<String, Scene> = parse(include_str!("fixtures/scenes.json"));
+    assert_eq!(scenes.len(), 2);
+
+    let scene = &scenes["4e1c6b20e-on-0"];
+    assert_eq!(scene.name, "Relax");
+    assert_eq!(scene.kind, scene::Kind::GroupScene);
+    assert_eq!(scene.group.as_deref(), Some("1"));
+    assert_eq!(scene.app_data.data.as_deref(), Some("myAppData"));
+    assert!(scene.last_update.is_some());
+    assert_eq!(scene.version, scene::Version::Post);
+
+    let scene = &scenes["3T2SvsxvwteNNys"];
+    assert_eq!(scene.kind, scene::Kind::LightScene);
+    assert_eq!(scene.owner, None);
+    assert!(scene.locked);
+    assert_eq!(scene.last_update, None);
+}
+
+#[test]
+fn sensors() {
+    let sensors: HashMap<String, Sensor> = parse(include_str!("fixtures/sensors.json"));
+    assert_eq!(sensors.len(), 3);
+
+    let sensor = &sensors["1"];
+    assert_eq!(sensor.type_name, "Daylight");
+    assert_eq!(sensor.state.daylight, Some(false));
+
+    let sensor = &sensors["2"];
+    assert_eq!(sensor.type_name, "ZLLPresence");
+    assert_eq!(sensor.state.presence, Some(false));
+    assert_eq!(sensor.config.battery, Some(100));
+    assert_eq!(sensor.config.sensitivity_max, Some(2));
+
+    let sensor = &sensors["3"];
+    assert_eq!(sensor.type_name, "CLIPGenericFlag");
+    assert_eq!(sensor.state.flag, Some(true));
+    assert_eq!(sensor.state.last_updated, None);
+}
+
+#[test]
+fn config() {
+    let config: Config = serde_json::from_str(include_str!("fixtures/config.json")).unwrap();
+    assert_eq!(config.name, "Philips hue");
+    assert_eq!(config.api_version, "1.40.0");
+    assert_eq!(config.zigbee_channel, 15);
+    assert_eq!(config.timezone.as_deref(), Some("Europe/Berlin"));
+    assert_eq!(
+        config.software_update.unwrap().state,
+        config::SoftwareUpdateState::NoUpdates
+    );
+    assert_eq!(config.whitelist.len(), 2);
+}
+
+#[test]
+fn capabilities() {
+    let capabilities: Capabilities =
+        serde_json::from_str(include_str!("fixtures/capabilities.json")).unwrap();
+    assert_eq!(capabilities.lights.total, 63);
+    assert_eq!(capabilities.streaming.channels, 10);
+    assert!(capabilities
+        .timezones
+        .values
+        .contains(&"Europe/Berlin".to_owned()));
+}